@@ -0,0 +1,56 @@
+//! Collects the solutions in `src/bin` (`01.rs` or `2023-01.rs`) so that the main binary can run them in-process.
+//! See `template::registry` for details.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut bins: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let bin = name.strip_suffix(".rs")?;
+            is_solution(bin).then(|| bin.to_string())
+        })
+        .collect();
+    bins.sort_unstable();
+
+    let mut out = String::new();
+
+    for bin in &bins {
+        let path = bin_dir.join(format!("{bin}.rs"));
+        out.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod {};\n\n",
+            path.to_str().unwrap(),
+            module_name(bin)
+        ));
+    }
+
+    out.push_str("pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for bin in &bins {
+        out.push_str(&format!("    {}::SOLUTION,\n", module_name(bin)));
+    }
+    out.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
+
+fn is_digits(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Solutions are named by day (`01`), or by year and day (`2023-01`).
+fn is_solution(bin: &str) -> bool {
+    match bin.split_once('-') {
+        Some((year, day)) => is_digits(year, 4) && is_digits(day, 2),
+        None => is_digits(bin, 2),
+    }
+}
+
+fn module_name(bin: &str) -> String {
+    format!("day_{}", bin.replace('-', "_"))
+}
@@ -34,7 +34,7 @@ fn count_stones_after_blinks(
 
     // Rule 2: Split stones with even number of digits
     let digits = engraving.ilog10() + 1;
-    if digits % 2 == 0 {
+    if digits.is_multiple_of(2) {
         let power = 10u64.pow(digits / 2);
         let left = engraving / power;
         let right = engraving % power;
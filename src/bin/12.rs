@@ -40,9 +40,8 @@ pub fn part_one(input: &str) -> Option<u64> {
                 if left_region.label == c {
                     new_perimeter -= 2;
 
-                    if representativ.is_some() {
+                    if let Some(upper_representative) = representativ {
                         let left_representative = representatives_grid[y][x - 1];
-                        let upper_representative = representativ.unwrap();
                         let left_region_idx = representatives[left_representative];
                         let upper_region_idx = representatives[upper_representative];
 
@@ -66,24 +65,26 @@ pub fn part_one(input: &str) -> Option<u64> {
                     }
                 }
             }
-            if representativ.is_none() {
-                let region = Region {
-                    label: c,
-                    replaced_by: None,
-                    area: 1,
-                    perimeter: new_perimeter,
-                };
-                regions.push(region);
-                representatives.push(regions.len() - 1);
-                representativ = Some(representatives.len() - 1);
-            } else {
-                let region = regions
-                    .get_mut(representatives[representativ.unwrap()])
-                    .unwrap();
-                region.area += 1;
-                region.perimeter += new_perimeter;
-            }
-            representatives_grid[y][x] = representativ.unwrap();
+            let representativ = match representativ {
+                Some(representativ) => {
+                    let region = regions.get_mut(representatives[representativ]).unwrap();
+                    region.area += 1;
+                    region.perimeter += new_perimeter;
+                    representativ
+                }
+                None => {
+                    let region = Region {
+                        label: c,
+                        replaced_by: None,
+                        area: 1,
+                        perimeter: new_perimeter,
+                    };
+                    regions.push(region);
+                    representatives.push(regions.len() - 1);
+                    representatives.len() - 1
+                }
+            };
+            representatives_grid[y][x] = representativ;
         }
     }
 
@@ -252,24 +253,26 @@ pub fn part_two(input: &str) -> Option<u64> {
                 }
             }
 
-            if representativ.is_none() {
-                let region = RegionP2 {
-                    label: c,
-                    replaced_by: None,
-                    area: 1,
-                    sides: 4,
-                };
-                regions.push(region);
-                representatives.push(regions.len() - 1);
-                representativ = Some(representatives.len() - 1);
-            } else {
-                let region = regions
-                    .get_mut(representatives[representativ.unwrap()])
-                    .unwrap();
-                region.area += 1;
-                region.sides += delta_sides;
-            }
-            representatives_grid[y][x] = representativ.unwrap();
+            let representativ = match representativ {
+                Some(representativ) => {
+                    let region = regions.get_mut(representatives[representativ]).unwrap();
+                    region.area += 1;
+                    region.sides += delta_sides;
+                    representativ
+                }
+                None => {
+                    let region = RegionP2 {
+                        label: c,
+                        replaced_by: None,
+                        area: 1,
+                        sides: 4,
+                    };
+                    regions.push(region);
+                    representatives.push(regions.len() - 1);
+                    representatives.len() - 1
+                }
+            };
+            representatives_grid[y][x] = representativ;
         }
     }
 
@@ -1,6 +1,7 @@
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, parse = parse_input);
 
-struct Machine {
+#[derive(Clone, Copy)]
+pub struct Machine {
     ax: i64,
     ay: i64,
     bx: i64,
@@ -71,19 +72,17 @@ fn play(mut machine: Machine, part_2: bool) -> i64 {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let machines = parse_input(input);
+pub fn part_one(machines: &[Machine]) -> Option<u64> {
     let mut result = 0;
-    for machine in machines {
+    for &machine in machines {
         result += play(machine, false);
     }
     Some(result as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let machines = parse_input(input);
+pub fn part_two(machines: &[Machine]) -> Option<u64> {
     let mut result = 0;
-    for machine in machines {
+    for &machine in machines {
         result += play(machine, true);
     }
     Some(result as u64)
@@ -95,7 +94,9 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(480));
     }
 }
@@ -1,13 +1,13 @@
 use advent_of_code::bin::util::bounding_box::BoundingBox;
 use advent_of_code::bin::util::point::Point;
+use advent_of_code::bin::util::recorder::Recorder;
 
 advent_of_code::solution!(14);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut robots: Vec<Point> = Vec::new();
     let mut velocities: Vec<Point> = Vec::new();
-    // let dimensions: Point = Point::new(11, 7); // for the example input
-    let dimensions: Point = Point::new(101, 103); // for the puzzle input
+    let dimensions = grid_dimensions();
     let simulation_steps = 100;
 
     for line in input.lines() {
@@ -68,6 +68,12 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(top_left_count * top_right_count * bottom_left_count * bottom_right_count)
 }
 
+/// The dimensions of the grid, 101x103 for the puzzle input (11x7 for the example).
+fn grid_dimensions() -> Point {
+    let params = params();
+    Point::new(params.get_or("width", 101), params.get_or("height", 103))
+}
+
 fn robot_unique_positions(robots: &[Point]) -> u32 {
     let mut unique_positions_set = std::collections::HashSet::new();
     for robot in robots {
@@ -79,8 +85,7 @@ fn robot_unique_positions(robots: &[Point]) -> u32 {
 pub fn part_two(input: &str) -> Option<u64> {
     let mut robots: Vec<Point> = Vec::new();
     let mut velocities: Vec<Point> = Vec::new();
-    // let dimensions: Point = Point::new(11, 7); // for the example input
-    let dimensions: Point = Point::new(101, 103); // for the puzzle input
+    let dimensions = grid_dimensions();
 
     for line in input.lines() {
         let parts: Vec<&str> = line.split_whitespace().collect();
@@ -100,6 +105,9 @@ pub fn part_two(input: &str) -> Option<u64> {
         velocities.push(Point::new(velocity[0], velocity[1]));
     }
 
+    let mut recorder = Recorder::new(puzzle(), "part-2", visualization());
+    recorder.record_points(dimensions.x, dimensions.y, &robots);
+
     let mut seconds = 0;
     loop {
         seconds += 1;
@@ -109,6 +117,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             robot.x %= dimensions.x;
             robot.y %= dimensions.y;
         }
+        recorder.record_points(dimensions.x, dimensions.y, &robots);
 
         let unique_positions = robot_unique_positions(&robots);
         if unique_positions == robots.len() as u32 {
@@ -116,14 +125,3 @@ pub fn part_two(input: &str) -> Option<u64> {
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    /* use super::*;
-
-    #[test] // when testing, change dimensions to 11, 7
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
-    }*/
-}
@@ -1,7 +1,8 @@
 use advent_of_code::bin::util::grid::Grid;
 use advent_of_code::bin::util::point::{parse_directions, Point, DOWN, LEFT, RIGHT, UP};
+use advent_of_code::bin::util::recorder::Recorder;
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(15, parse = parse);
 
 fn parse(input: &str) -> (Grid<u8>, Vec<Point>) {
     let mut parts = input.split("\n\n");
@@ -10,12 +11,15 @@ fn parse(input: &str) -> (Grid<u8>, Vec<Point>) {
     (grid, directions)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (mut grid, directions) = parse(input);
+pub fn part_one((grid, directions): &(Grid<u8>, Vec<Point>)) -> Option<u64> {
+    let mut grid = grid.clone();
     let mut position = grid.find(b'@').unwrap();
+    let mut recorder = Recorder::new(puzzle(), "part-1", visualization());
+    recorder.record_grid(&grid);
 
-    for direction in directions {
+    for &direction in directions {
         push_single(&mut grid, &mut position, direction);
+        recorder.record_grid(&grid);
     }
 
     let mut result = 0;
@@ -109,15 +113,16 @@ fn widen_grid(grid: &Grid<u8>) -> Grid<u8> {
     new_grid
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (mut grid, directions) = parse(input);
-    grid = widen_grid(&grid);
+pub fn part_two((grid, directions): &(Grid<u8>, Vec<Point>)) -> Option<u64> {
+    let mut grid = widen_grid(grid);
     let mut position = grid.find(b'@').unwrap();
 
     let mut boxes_to_move: Vec<Point> = Vec::new();
     let mut seen = Grid::new(grid.width(), grid.height(), u16::MAX);
+    let mut recorder = Recorder::new(puzzle(), "part-2", visualization());
+    recorder.record_grid(&grid);
 
-    for (seen_id, direction) in directions.into_iter().enumerate() {
+    for (seen_id, direction) in directions.iter().copied().enumerate() {
         match direction {
             UP | DOWN => push_wide(
                 &mut grid,
@@ -130,6 +135,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             LEFT | RIGHT => push_single(&mut grid, &mut position, direction),
             _ => unreachable!(),
         }
+        recorder.record_grid(&grid);
     }
 
     let mut result = 0;
@@ -147,13 +153,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(10092));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(9021));
     }
 }
@@ -1,158 +1,43 @@
 use advent_of_code::bin::util::grid::Grid;
 use advent_of_code::bin::util::point::{Point, RIGHT};
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use advent_of_code::bin::util::search::{dijkstra, Search};
+use std::collections::HashSet;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, parse = Grid::parse_ascii);
 
-struct State {
-    position: Point,
-    direction: Point,
-    cost: u32,
-}
-
-impl Eq for State {}
-
-impl PartialEq<Self> for State {
-    fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost
-    }
-}
-
-impl PartialOrd<Self> for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for State {
-    // Reverse ordering so that the smallest element is at the top of the heap
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
-fn bfs(grid: &Grid<u8>, start: Point, goal: Point) -> Option<u64> {
-    let mut heap = BinaryHeap::new();
-    let mut visited = Grid::new(grid.width(), grid.height(), false);
-    heap.push(State {
-        position: start,
-        direction: RIGHT,
-        cost: 0,
-    });
-
-    while let Some(State {
-        position,
-        direction,
-        cost,
-    }) = heap.pop()
-    {
-        if position == goal {
-            return Some(cost as u64);
-        }
-        if visited[position] {
-            continue;
-        }
-        visited[position] = true;
-
-        for (direction, price) in [
-            (direction, 1),
-            (direction.clockwise(), 1001),
-            (direction.counter_clockwise(), 1001),
-        ] {
-            let next = position + direction;
-            if grid[next] == b'#' {
-                continue;
-            }
-            heap.push(State {
-                position: next,
-                direction,
-                cost: cost + price,
-            });
-        }
-    }
-    None
-}
-
-fn bfs_cost_grid(grid: &Grid<u8>, start: Point, goal: Point) -> Grid<u32> {
-    let mut cost_grid = Grid::new(grid.width(), grid.height(), 0);
-    let mut heap = BinaryHeap::new();
-    let mut visited = Grid::new(grid.width(), grid.height(), false);
-    heap.push(State {
-        position: start,
-        direction: RIGHT,
-        cost: 0,
-    });
-
-    while let Some(State {
-        position,
-        direction,
-        cost,
-    }) = heap.pop()
-    {
-        if visited[position] {
-            continue;
-        }
-
-        cost_grid[position] = cost;
-        if position == goal && direction != RIGHT {
-            cost_grid[position] = cost + 1000;
-        }
-
-        visited[position] = true;
-
-        for (direction, price) in [
-            (direction, 1),
-            (direction.clockwise(), 1001),
-            (direction.counter_clockwise(), 1001),
-        ] {
-            let next = position + direction;
-
-            if grid[next] == b'#' {
-                continue;
-            }
-            heap.push(State {
-                position: next,
-                direction,
-                cost: cost + price,
-            });
-        }
-    }
-    cost_grid
-}
-
-fn find_points_on_shortest_paths(grid: &Grid<u8>, start: Point, goal: Point) -> Option<u64> {
-    let forward_costs = bfs_cost_grid(grid, start, goal);
-    let backward_costs = bfs_cost_grid(grid, goal, start);
-    let shortest_path_cost = forward_costs[goal];
-    let mut result = 0;
-
-    for combined_cost in forward_costs
-        .iter()
-        .zip(backward_costs.iter())
-        .map(|(a, b)| *a + *b)
-    {
-        if combined_cost == shortest_path_cost || combined_cost == shortest_path_cost - 1000 {
-            result += 1;
-        }
-    }
-    Some(result)
-}
-
-pub fn part_one(input: &str) -> Option<u64> {
-    let grid = Grid::parse_ascii(input);
+/// Searches the cheapest paths through the maze, where a state is a position and a direction.
+fn search(grid: &Grid<u8>) -> Search<(Point, Point)> {
     let start = Point::new(1, grid.height() - 2);
     let goal = Point::new(grid.width() - 2, 1);
 
-    bfs(&grid, start, goal)
+    dijkstra(
+        [(start, RIGHT)],
+        |&(position, direction)| {
+            [
+                (direction, 1),
+                (direction.clockwise(), 1001),
+                (direction.counter_clockwise(), 1001),
+            ]
+            .into_iter()
+            .map(move |(direction, price)| ((position + direction, direction), price))
+            .filter(|((next, _), _)| grid[*next] != b'#')
+        },
+        |&(position, _)| position == goal,
+    )
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let grid = Grid::parse_ascii(input);
-    let start = Point::new(1, grid.height() - 2);
-    let goal = Point::new(grid.width() - 2, 1);
+pub fn part_one(grid: &Grid<u8>) -> Option<u64> {
+    search(grid).goal_distance()
+}
+
+pub fn part_two(grid: &Grid<u8>) -> Option<u64> {
+    let positions: HashSet<Point> = search(grid)
+        .nodes_on_paths()
+        .into_iter()
+        .map(|(position, _)| position)
+        .collect();
 
-    find_points_on_shortest_paths(&grid, start, goal)
+    Some(positions.len() as u64)
 }
 
 #[cfg(test)]
@@ -161,13 +46,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&Grid::parse_ascii(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(11048));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&Grid::parse_ascii(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(64));
     }
 }
@@ -1,183 +1,78 @@
 use advent_of_code::bin::util::grid::Grid;
-use advent_of_code::bin::util::point::{
-    parse_direction, Point, DIRECTIONS_ORTHOGONAL, DOWN, LEFT, RIGHT, UP,
-};
-use std::collections::VecDeque;
+use advent_of_code::bin::util::point::Point;
+use advent_of_code::bin::util::search::bfs_grid;
 use std::str::FromStr;
 
 advent_of_code::solution!(18);
 
-fn bfs(grid: &mut Grid<u8>, start: Point, end: Point) -> Option<u64> {
-    let mut queue = VecDeque::new();
-
-    queue.push_back((start, 0));
-
-    while let Some((pos, steps)) = queue.pop_front() {
-        if pos == end {
-            return Some(steps);
-        }
-        for dir in DIRECTIONS_ORTHOGONAL {
-            let new_pos = pos + dir;
-            if let Some(b'.') = grid.get(new_pos) {
-                grid[new_pos] = b'X';
-                queue.push_back((new_pos, steps + 1));
-            }
-        }
-    }
-
-    None
+/// The empty memory space and the number of bytes that have fallen,
+/// 71x71 and 1024 bytes for the puzzle input (7x7 and 12 bytes for the example).
+fn memory_space() -> (Grid<u8>, usize) {
+    let params = params();
+    let size = params.get_or("size", 71);
+    (Grid::new(size, size, b'.'), params.get_or("bytes", 1024))
 }
 
-fn highlight_shortest_path(
-    grid: &mut Grid<u8>,
-    visited: &mut Grid<bool>,
-    on_shortest_route: &mut Grid<bool>,
-    start: Point,
-    end: Point,
-) -> bool {
-    let mut queue = VecDeque::new();
+/// Drops the first `bytes` bytes into the memory space and searches the shortest path from the top left to the
+/// bottom right corner.
+fn shortest_path(lines: &[&str], bytes: usize) -> Option<u64> {
+    let (mut grid, _) = memory_space();
 
-    // reset
-    on_shortest_route.reset(false);
-    visited.reset(false);
-
-    queue.push_back((start, 0));
-
-    let mut found = false;
-
-    while let Some((pos, steps)) = queue.pop_front() {
-        if pos == end {
-            found = true;
-            break;
-        }
-
-        for dir in DIRECTIONS_ORTHOGONAL {
-            let new_pos = pos + dir;
-            // Check if the position is valid and not visited
-            if let Some(val) = grid.get(new_pos) {
-                if *val != b'#' && !visited[new_pos] {
-                    visited[new_pos] = true; // Mark as visited before adding to queue
-                    match dir {
-                        UP => grid[new_pos] = b'v',
-                        DOWN => grid[new_pos] = b'^',
-                        LEFT => grid[new_pos] = b'>',
-                        RIGHT => grid[new_pos] = b'<',
-                        _ => unreachable!(),
-                    }
-                    queue.push_back((new_pos, steps + 1));
-                }
-            }
-        }
-    }
-
-    if !found {
-        return false;
-    }
-
-    let mut pos = end;
-    while pos != start {
-        if let Some(dir) = parse_direction(grid[pos]) {
-            on_shortest_route[pos] = true;
-            pos += dir;
-        } else {
-            break;
-        }
-    }
-
-    true
-}
-
-pub fn part_one(input: &str) -> Option<u64> {
-    // let mut grid = Grid::new(7, 7, b'.'); // for the example
-    let mut grid = Grid::new(71, 71, b'.');
-
-    // let first_x_elements = 12; // for the example
-    let first_x_elements = 1024; // for the input
-
-    for line in input.lines().take(first_x_elements) {
+    for line in lines.iter().take(bytes) {
         let pos = Point::from_str(line).unwrap();
         grid[pos] = b'#';
     }
 
     let start = Point::new(0, 0);
     let end = Point::new(grid.width() - 1, grid.height() - 1);
-    bfs(&mut grid, start, end)
+    bfs_grid(&grid, start, end, b"#").goal_distance()
 }
 
-pub fn part_two(input: &str) -> Option<&str> {
+pub fn part_one(input: &str) -> Option<u64> {
     let lines: Vec<&str> = input.lines().collect();
+    let (_, first_x_elements) = memory_space();
 
-    // let mut grid = Grid::new(7, 7, b'.'); // for the example
-    let mut grid = Grid::new(71, 71, b'.');
+    shortest_path(&lines, first_x_elements)
+}
 
-    // let first_x_elements = 12; // for the example
-    let first_x_elements = 1024; // for the input
+pub fn part_two(input: &str) -> Option<&str> {
+    let lines: Vec<&str> = input.lines().collect();
+    let (_, first_x_elements) = memory_space();
 
-    for line in lines.iter().take(first_x_elements) {
-        let pos = Point::from_str(line).unwrap();
-        grid[pos] = b'#';
+    if shortest_path(&lines, lines.len()).is_some() {
+        return None;
     }
 
-    let start = Point::new(0, 0);
-    let end = Point::new(grid.width() - 1, grid.height() - 1);
-
-    let mut on_shortest_route = Grid::new(grid.width(), grid.height(), false);
-    let mut visited = Grid::new(grid.width(), grid.height(), false);
-
+    // Binary search for the fewest bytes that block the path
     let mut left = first_x_elements;
     let mut right = lines.len();
-    let mut last_valid_config = grid;
-    let mut breaking_line = None;
 
     while left < right {
         let mid = left + (right - left) / 2;
-        let mut test_grid = last_valid_config.clone();
-
-        // Add walls from left to mid
-        for line in lines.iter().take(mid).skip(left) {
-            let pos = Point::from_str(line).unwrap();
-            test_grid[pos] = b'#';
-        }
-
-        if highlight_shortest_path(
-            &mut test_grid,
-            &mut visited,
-            &mut on_shortest_route,
-            start,
-            end,
-        ) {
-            // Configuration is valid, try adding more walls
-            last_valid_config = test_grid;
+        if shortest_path(&lines, mid).is_some() {
             left = mid + 1;
         } else {
-            // Configuration breaks the path, need to try fewer walls
             right = mid;
-            breaking_line = Some(mid);
         }
     }
 
-    // Return the first line that breaks the path
-    if let Some(line) = breaking_line {
-        Some(lines[line - 1])
-    } else {
-        None
-    }
+    Some(lines[left - 1])
 }
 
 #[cfg(test)]
 mod tests {
-    /*use super::*;
+    use super::*;
+    use advent_of_code::template::params::{self, Params};
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(22));
-    }
-
-    #[test]
-    fn test_part_two() {
+    fn test_part_two_from_any_number_of_fallen_bytes() {
+        // The binary search has to find the same byte no matter where it starts.
         let binding = advent_of_code::template::read_file("examples", DAY);
-        let result = part_two(&binding);
-        assert_eq!(result, Some("6,1"));
-    }*/
+        for bytes in 0..=20 {
+            let bytes = bytes.to_string();
+            let params = Params::from([("size", "7"), ("bytes", bytes.as_str())]);
+            let result = params::with(&params, || part_two(&binding));
+            assert_eq!(result, Some("6,1"), "after {bytes} bytes");
+        }
+    }
 }
@@ -1,6 +1,9 @@
-use std::intrinsics::write_bytes;
+use std::ptr::write_bytes;
 // 2d grid backed by a single vector. Designed to be used with the Point struct.
 use crate::bin::util::point::*;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
 use std::ops::{Index, IndexMut};
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -10,20 +13,105 @@ pub struct Grid<T> {
     data: Vec<T>,
 }
 
+/// Conversion of a single byte of an ASCII grid into a cell, see [`Grid::parse`].
+pub trait FromCell: Sized {
+    /// The cell of a byte, or `None` if the byte is not a valid cell.
+    fn from_cell(byte: u8) -> Option<Self>;
+}
+
+impl FromCell for u8 {
+    #[inline]
+    fn from_cell(byte: u8) -> Option<Self> {
+        Some(byte)
+    }
+}
+
+impl FromCell for char {
+    #[inline]
+    fn from_cell(byte: u8) -> Option<Self> {
+        Some(char::from(byte))
+    }
+}
+
+// `#` is `true` and `.` is `false`.
+impl FromCell for bool {
+    #[inline]
+    fn from_cell(byte: u8) -> Option<Self> {
+        match byte {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseGridError {
+    Empty,
+    /// A row has a different width than the first row.
+    Ragged {
+        row: i32,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        point: Point,
+        byte: u8,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "the grid is empty."),
+            ParseGridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} has {found} cells, expected {expected}."),
+            ParseGridError::InvalidCell { point, byte } => write!(
+                f,
+                "invalid cell `{}` at {},{}.",
+                byte.escape_ascii(),
+                point.x,
+                point.y
+            ),
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+/// The positions of marker bytes like `S` and `E` in a grid, see [`Grid::parse_with_markers`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Markers(HashMap<u8, Vec<Point>>);
+
+impl Markers {
+    /// The first position of a marker, row by row.
+    #[inline]
+    pub fn get(&self, marker: u8) -> Option<Point> {
+        self.all(marker).first().copied()
+    }
+
+    /// All positions of a marker, row by row.
+    #[inline]
+    pub fn all(&self, marker: u8) -> &[Point] {
+        self.0.get(&marker).map_or(&[], Vec::as_slice)
+    }
+}
+
 impl Grid<u8> {
+    /// # Panics
+    /// If the input is empty or its rows have different widths, see [`Grid::parse`].
     #[must_use]
     #[inline]
     pub fn parse_ascii(input: &str) -> Self {
-        let bytes: Vec<_> = input.lines().map(str::as_bytes).collect();
-        let height = bytes.len() as i32;
-        let width = bytes[0].len() as i32;
-        let mut data = Vec::with_capacity((width * height) as usize);
-        bytes.iter().for_each(|line| data.extend_from_slice(line));
-        Self {
-            width,
-            height,
-            data,
-        }
+        Self::parse(input).unwrap_or_else(|e| panic!("invalid grid: {e}"))
+    }
+
+    /// Parses a grid of decimal digits into their values, e.g. heights.
+    pub fn parse_digits(input: &str) -> Result<Self, ParseGridError> {
+        Self::parse_with(input, |byte| byte.is_ascii_digit().then_some(byte - b'0'))
     }
 
     pub fn print(&self) {
@@ -45,6 +133,78 @@ impl Grid<bool> {
 }
 
 impl<T> Grid<T> {
+    /// Parses an ASCII grid with one row per line into cells of type `T`.
+    pub fn parse(input: &str) -> Result<Self, ParseGridError>
+    where
+        T: FromCell,
+    {
+        Self::parse_with(input, T::from_cell)
+    }
+
+    /// Parses an ASCII grid with one row per line, converting every byte with `parse`.
+    pub fn parse_with(
+        input: &str,
+        mut parse: impl FnMut(u8) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut data = Vec::with_capacity(input.len());
+
+        for (y, line) in input.lines().enumerate() {
+            let expected = *width.get_or_insert(line.len());
+            if line.len() != expected {
+                return Err(ParseGridError::Ragged {
+                    row: y as i32,
+                    expected,
+                    found: line.len(),
+                });
+            }
+
+            for (x, byte) in line.bytes().enumerate() {
+                let cell = parse(byte).ok_or(ParseGridError::InvalidCell {
+                    point: Point::new(x as i32, y as i32),
+                    byte,
+                })?;
+                data.push(cell);
+            }
+            height += 1;
+        }
+
+        match width {
+            None | Some(0) => Err(ParseGridError::Empty),
+            Some(width) => Ok(Self {
+                width: width as i32,
+                height,
+                data,
+            }),
+        }
+    }
+
+    /// Parses an ASCII grid like [`Grid::parse`] and returns the positions of the given marker bytes.
+    /// Markers are converted like every other byte, so `T` decides what a marker stands on, e.g. floor for `S` and `E`.
+    pub fn parse_with_markers(
+        input: &str,
+        markers: &[u8],
+    ) -> Result<(Self, Markers), ParseGridError>
+    where
+        T: FromCell,
+    {
+        let grid = Self::parse(input)?;
+
+        let mut found = Markers::default();
+        for (point, byte) in input.lines().enumerate().flat_map(|(y, line)| {
+            line.bytes()
+                .enumerate()
+                .map(move |(x, byte)| (Point::new(x as i32, y as i32), byte))
+        }) {
+            if markers.contains(&byte) {
+                found.0.entry(byte).or_default().push(point);
+            }
+        }
+
+        Ok((grid, found))
+    }
+
     #[must_use]
     #[inline]
     pub fn new(width: i32, height: i32, fill: T) -> Self
@@ -73,6 +233,16 @@ impl<T> Grid<T> {
         self.data.iter().position(|&x| x == to_find).map(to_point)
     }
 
+    /// All points with the given value, row by row.
+    pub fn find_all(&self, to_find: T) -> impl Iterator<Item = Point> + '_
+    where
+        T: PartialEq + Copy,
+    {
+        self.iter_with_points()
+            .filter(move |(_, &value)| value == to_find)
+            .map(|(point, _)| point)
+    }
+
     #[inline]
     pub fn swap(&mut self, a: Point, b: Point) {
         self.data.swap(
@@ -119,6 +289,157 @@ impl<T> Grid<T> {
             None
         }
     }
+
+    /// The neighbours of a point in the given directions that are within the grid.
+    #[inline]
+    pub fn neighbours_in<'a>(
+        &'a self,
+        point: Point,
+        directions: &'a [Point],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions
+            .iter()
+            .map(move |&direction| point + direction)
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// The orthogonal neighbours of a point that are within the grid.
+    #[inline]
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &DIRECTIONS_ORTHOGONAL)
+    }
+
+    /// The orthogonal and diagonal neighbours of a point that are within the grid.
+    #[inline]
+    pub fn neighbours_all(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &DIRECTIONS_ALL)
+    }
+
+    /// The cells of a single row.
+    ///
+    /// # Panics
+    /// If `y` is outside of the grid.
+    #[inline]
+    pub fn row(&self, y: i32) -> &[T] {
+        assert!(
+            (0..self.height).contains(&y),
+            "row {y} is outside of the grid"
+        );
+        let start = (y * self.width) as usize;
+        &self.data[start..start + self.width as usize]
+    }
+
+    /// The cells of a single column.
+    ///
+    /// # Panics
+    /// If `x` is outside of the grid.
+    #[inline]
+    pub fn column(&self, x: i32) -> impl Iterator<Item = &T> {
+        assert!(
+            (0..self.width).contains(&x),
+            "column {x} is outside of the grid"
+        );
+        self.data
+            .iter()
+            .skip(x as usize)
+            .step_by(self.width as usize)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks_exact(self.width as usize)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All points that are orthogonally connected to `start` and have the same value, including `start`.
+    pub fn flood_fill(&self, start: Point) -> Vec<Point>
+    where
+        T: PartialEq,
+    {
+        let mut visited = Grid::new(self.width, self.height, false);
+        let mut points = vec![];
+        self.fill(start, &mut visited, |point, _| points.push(point));
+        points
+    }
+
+    /// Labels all orthogonally connected regions of equal values.
+    /// Returns a grid with the index of the region of every point, and the regions.
+    pub fn regions(&self) -> (Grid<usize>, Vec<Region<T>>)
+    where
+        T: PartialEq + Copy,
+    {
+        let mut labels = Grid::new(self.width, self.height, usize::MAX);
+        let mut visited = Grid::new(self.width, self.height, false);
+        let mut regions = vec![];
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let start = Point::new(x, y);
+                if visited[start] {
+                    continue;
+                }
+
+                let mut region = Region {
+                    value: self[start],
+                    start,
+                    area: 0,
+                    perimeter: 0,
+                };
+
+                self.fill(start, &mut visited, |point, same_neighbours| {
+                    labels[point] = regions.len();
+                    region.area += 1;
+                    region.perimeter += 4 - same_neighbours;
+                });
+
+                regions.push(region);
+            }
+        }
+
+        (labels, regions)
+    }
+
+    /// Visits all points of the region of `start` that were not visited yet,
+    /// together with their number of orthogonal neighbours in the same region.
+    fn fill(&self, start: Point, visited: &mut Grid<bool>, mut visit: impl FnMut(Point, u32))
+    where
+        T: PartialEq,
+    {
+        let value = &self[start];
+        let mut stack = vec![start];
+        visited[start] = true;
+
+        while let Some(point) = stack.pop() {
+            let mut same_neighbours = 0;
+
+            for neighbour in self.neighbours(point) {
+                if self[neighbour] != *value {
+                    continue;
+                }
+
+                same_neighbours += 1;
+                if !visited[neighbour] {
+                    visited[neighbour] = true;
+                    stack.push(neighbour);
+                }
+            }
+
+            visit(point, same_neighbours);
+        }
+    }
+}
+
+/// An orthogonally connected region of equal values, see [`Grid::regions`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Region<T> {
+    pub value: T,
+    /// The first point of the region, row by row.
+    pub start: Point,
+    pub area: u32,
+    /// The number of cell edges that border another region or the edge of the grid.
+    pub perimeter: u32,
 }
 
 impl<T> Index<Point> for Grid<T> {
@@ -136,3 +457,142 @@ impl<T> IndexMut<Point> for Grid<T> {
         &mut self.data[(point.y * self.width + point.x) as usize]
     }
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{FromCell, Grid, ParseGridError, Region};
+    use crate::bin::util::point::Point;
+
+    const EXAMPLE: &str = "AAAA\nBBCD\nBBCC\nEEEC";
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = Grid::parse_ascii(EXAMPLE);
+        assert_eq!(grid.neighbours(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours_all(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours_all(Point::new(3, 1)).count(), 5);
+        assert_eq!(grid.neighbours_all(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = Grid::parse_ascii(EXAMPLE);
+        assert_eq!(grid.row(1), b"BBCD");
+        assert_eq!(grid.rows().count(), 4);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), b"ACCE");
+        assert_eq!(grid.columns().count(), 4);
+        assert_eq!(grid.column(3).copied().collect::<Vec<_>>(), b"ADCC");
+        assert_eq!(
+            grid.find_all(b'C').collect::<Vec<_>>(),
+            vec![
+                Point::new(2, 1),
+                Point::new(2, 2),
+                Point::new(3, 2),
+                Point::new(3, 3)
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column 4 is outside of the grid")]
+    fn rejects_columns_outside_of_the_grid() {
+        Grid::parse_ascii(EXAMPLE).column(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "row -1 is outside of the grid")]
+    fn rejects_rows_outside_of_the_grid() {
+        Grid::parse_ascii(EXAMPLE).row(-1);
+    }
+
+    #[test]
+    fn labels_regions() {
+        let grid = Grid::parse_ascii(EXAMPLE);
+        assert_eq!(grid.flood_fill(Point::new(3, 3)).len(), 4);
+
+        let (labels, regions) = grid.regions();
+        assert_eq!(regions.len(), 5);
+        assert_eq!(
+            regions[2],
+            Region {
+                value: b'C',
+                start: Point::new(2, 1),
+                area: 4,
+                perimeter: 10
+            }
+        );
+        assert_eq!(labels[Point::new(3, 3)], 2);
+        assert_eq!(
+            regions.iter().map(|r| r.area * r.perimeter).sum::<u32>(),
+            140
+        );
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    enum Tile {
+        Wall,
+        Floor,
+        Box,
+    }
+
+    impl FromCell for Tile {
+        fn from_cell(byte: u8) -> Option<Self> {
+            match byte {
+                b'#' => Some(Tile::Wall),
+                b'.' | b'S' | b'E' => Some(Tile::Floor),
+                b'O' => Some(Tile::Box),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn parses_typed_cells() {
+        let (grid, markers) =
+            Grid::<Tile>::parse_with_markers("####\n#SO#\n#.E#\n####\n", b"SE").unwrap();
+        assert_eq!(grid[Point::new(1, 1)], Tile::Floor);
+        assert_eq!(grid[Point::new(2, 1)], Tile::Box);
+        assert_eq!(grid[Point::new(0, 2)], Tile::Wall);
+        assert_eq!(markers.get(b'S'), Some(Point::new(1, 1)));
+        assert_eq!(markers.all(b'E'), &[Point::new(2, 2)]);
+        assert_eq!(markers.get(b'X'), None);
+
+        let heights = Grid::parse_digits("0123\n9876").unwrap();
+        assert_eq!(heights.row(1), &[9, 8, 7, 6]);
+        assert_eq!(
+            Grid::<bool>::parse("#.\n.#")
+                .unwrap()
+                .find_all(true)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!(Grid::<u8>::parse(""), Err(ParseGridError::Empty));
+        assert_eq!(Grid::<u8>::parse("\n\n"), Err(ParseGridError::Empty));
+        assert_eq!(
+            Grid::<u8>::parse("...\n..\n..."),
+            Err(ParseGridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+
+        let error = Grid::<Tile>::parse("#.\n#x").unwrap_err();
+        assert_eq!(
+            error,
+            ParseGridError::InvalidCell {
+                point: Point::new(1, 1),
+                byte: b'x'
+            }
+        );
+        assert_eq!(error.to_string(), "invalid cell `x` at 1,1.");
+        assert!(Grid::parse_digits("12\n3a").is_err());
+    }
+}
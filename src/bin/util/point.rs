@@ -11,6 +11,22 @@ pub const DOWN: Point = Point::new(0, 1);
 pub const LEFT: Point = Point::new(-1, 0);
 pub const RIGHT: Point = Point::new(1, 0);
 pub const DIRECTIONS_ORTHOGONAL: [Point; 4] = [UP, DOWN, LEFT, RIGHT];
+pub const DIRECTIONS_DIAGONAL: [Point; 4] = [
+    Point::new(-1, -1),
+    Point::new(1, -1),
+    Point::new(-1, 1),
+    Point::new(1, 1),
+];
+pub const DIRECTIONS_ALL: [Point; 8] = [
+    UP,
+    DOWN,
+    LEFT,
+    RIGHT,
+    Point::new(-1, -1),
+    Point::new(1, -1),
+    Point::new(-1, 1),
+    Point::new(1, 1),
+];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point {
@@ -41,6 +57,12 @@ impl Point {
     pub fn scale(self, factor: i32) -> Self {
         Point::new(self.x * factor, self.y * factor)
     }
+
+    #[inline]
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
 }
 
 impl FromStr for Point {
@@ -64,7 +86,6 @@ impl Add for Point {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn add(self, rhs: Self) -> Self {
         Point::new(self.x + rhs.x, self.y + rhs.y)
     }
@@ -82,7 +103,6 @@ impl Mul<i32> for Point {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn mul(self, rhs: i32) -> Self {
         Point::new(self.x * rhs, self.y * rhs)
     }
@@ -92,7 +112,6 @@ impl Sub for Point {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn sub(self, rhs: Self) -> Self {
         Point::new(self.x - rhs.x, self.y - rhs.y)
     }
@@ -0,0 +1,506 @@
+// Frame recorder for visualizing simulations. Solutions feed grids, renderers or point sets into a recorder, which
+// is only active when the solution is run with `--visualize <gif|frames|play>` (see `template::visualize`), so
+// recording costs nothing otherwise. Once the recorder is dropped, the frames are written to an animated GIF or a
+// numbered frame directory in `data/<year>/visualizations`, or replayed in the terminal at the `--fps` frame rate.
+use crate::bin::util::grid::Grid;
+use crate::bin::util::point::Point;
+use crate::bin::util::render::{Cell, Color, Renderer};
+use crate::template::visualize::{Output, Visualization};
+use crate::template::PuzzleId;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+/// Only the last frames are kept, so that long simulations do not run out of memory.
+pub const MAX_FRAMES: usize = 1_000;
+/// The number of pixels per cell in images.
+const SCALE: u32 = 4;
+
+pub struct Recorder {
+    puzzle: PuzzleId,
+    name: String,
+    visualization: Option<Visualization>,
+    frames: VecDeque<Grid<Cell>>,
+    dropped: usize,
+}
+
+impl Recorder {
+    /// A recorder for a part of a puzzle, e.g. `part-2`. Solutions pass the `visualization()` generated by the
+    /// `solution!` macro, so that it is only enabled when run with `--visualize`.
+    #[must_use]
+    pub fn new(puzzle: PuzzleId, name: &str, visualization: Option<Visualization>) -> Self {
+        Self {
+            puzzle,
+            name: name.to_string(),
+            visualization,
+            frames: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.visualization.is_some()
+    }
+
+    pub fn frames(&self) -> impl Iterator<Item = &Grid<Cell>> {
+        self.frames.iter()
+    }
+
+    /// Removes the recorded frames, so that they are not written when the recorder is dropped.
+    pub fn take_frames(&mut self) -> Vec<Grid<Cell>> {
+        self.dropped = 0;
+        self.frames.drain(..).collect()
+    }
+
+    /// Records the cells of a renderer, including its overlays.
+    #[inline]
+    pub fn record<T>(&mut self, renderer: &Renderer<T>) {
+        if self.is_enabled() {
+            self.push(renderer.cells());
+        }
+    }
+
+    /// Records a renderer that is only built if the recorder is enabled.
+    #[inline]
+    pub fn record_with<'a, T: 'a>(&mut self, renderer: impl FnOnce() -> Renderer<'a, T>) {
+        if self.is_enabled() {
+            self.push(renderer().cells());
+        }
+    }
+
+    /// Records a byte grid as it is, see [`Grid::renderer`].
+    #[inline]
+    pub fn record_grid(&mut self, grid: &Grid<u8>) {
+        if self.is_enabled() {
+            self.push(grid.renderer().cells());
+        }
+    }
+
+    /// Records a set of points in a grid of the given size, e.g. the positions of robots.
+    #[inline]
+    pub fn record_points<'a>(
+        &mut self,
+        width: i32,
+        height: i32,
+        points: impl IntoIterator<Item = &'a Point>,
+    ) {
+        if self.is_enabled() {
+            let mut grid = Grid::new(width, height, false);
+            for &point in points {
+                if let Some(cell) = grid.get_mut(point) {
+                    *cell = true;
+                }
+            }
+            self.push(grid.renderer().cells());
+        }
+    }
+
+    fn push(&mut self, frame: Grid<Cell>) {
+        if self.frames.len() == MAX_FRAMES {
+            self.frames.pop_front();
+            self.dropped += 1;
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Writes or replays the recorded frames and clears them. Called when the recorder is dropped.
+    pub fn finish(&mut self) -> io::Result<()> {
+        let Some(visualization) = self.visualization else {
+            return Ok(());
+        };
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+
+        if self.dropped > 0 {
+            eprintln!(
+                "Dropped the first {} frames, only the last {MAX_FRAMES} are kept.",
+                self.dropped
+            );
+        }
+
+        let frames = self.take_frames();
+
+        match visualization.output {
+            Output::Gif => {
+                let path = self.path("gif");
+                write_file(&path, &gif::encode(&frames, SCALE, visualization.fps))?;
+                eprintln!("Wrote {} frames to \"{}\".", frames.len(), path.display());
+            }
+            Output::Frames => {
+                let dir = self.path("");
+                if dir.exists() {
+                    fs::remove_dir_all(&dir)?;
+                }
+                for (i, frame) in frames.iter().enumerate() {
+                    let path = dir.join(format!("{:04}.png", i + 1));
+                    write_file(&path, &Renderer::new(frame, |cell| *cell).to_png(SCALE))?;
+                }
+                eprintln!("Wrote {} frames to \"{}\".", frames.len(), dir.display());
+            }
+            Output::Play => play(&frames, visualization.frame_duration())?,
+        }
+
+        Ok(())
+    }
+
+    /// The output path in `data/<year>/visualizations`. Without an extension, the path of a directory.
+    fn path(&self, extension: &str) -> PathBuf {
+        let name = format!("{}-{}", self.puzzle.day, self.name);
+        let dir = self.puzzle.data_dir("visualizations");
+        if extension.is_empty() {
+            dir.join(name)
+        } else {
+            dir.join(format!("{name}.{extension}"))
+        }
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            eprintln!("Failed to write visualization: {e}");
+        }
+    }
+}
+
+fn write_file(path: &PathBuf, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Replays frames in the terminal, redrawing the screen for every frame.
+/// Frames go to stderr, as stdout carries the reports of the solution.
+fn play(frames: &[Grid<Cell>], frame_duration: Duration) -> io::Result<()> {
+    let mut stderr = io::stderr().lock();
+    for (i, frame) in frames.iter().enumerate() {
+        let ansi = Renderer::new(frame, |cell| *cell).to_ansi();
+        writeln!(
+            stderr,
+            "\x1b[2J\x1b[H{ansi}Frame {}/{}",
+            i + 1,
+            frames.len()
+        )?;
+        stderr.flush()?;
+        thread::sleep(frame_duration);
+    }
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+// Minimal encoder for animated GIFs with a global palette of up to 256 colors. Colors beyond that are
+// mapped to the closest color of the palette.
+mod gif {
+    use super::{Cell, Color, Grid, HashMap};
+
+    const MAX_CODE: u16 = 4095;
+
+    pub fn encode(frames: &[Grid<Cell>], scale: u32, fps: f64) -> Vec<u8> {
+        let width = frames.iter().map(Grid::width).max().unwrap_or(0) as u32 * scale;
+        let height = frames.iter().map(Grid::height).max().unwrap_or(0) as u32 * scale;
+        let palette = palette(frames);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let delay = (100.0 / fps).round().max(1.0) as u16;
+
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend((width as u16).to_le_bytes());
+        gif.extend((height as u16).to_le_bytes());
+        // global color table of 256 colors, 8 bits per channel.
+        gif.extend([0xf7, 0, 0]);
+        for index in 0..256 {
+            let Color { r, g, b } = palette.colors.get(index).copied().unwrap_or(Color::BLACK);
+            gif.extend([r, g, b]);
+        }
+
+        // loop forever.
+        gif.extend([0x21, 0xff, 0x0b]);
+        gif.extend(b"NETSCAPE2.0");
+        gif.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        let mut indices = HashMap::new();
+        for frame in frames {
+            gif.extend([0x21, 0xf9, 0x04, 0x00]);
+            gif.extend(delay.to_le_bytes());
+            gif.extend([0x00, 0x00]);
+
+            gif.push(0x2c);
+            gif.extend([0, 0, 0, 0]);
+            gif.extend((width as u16).to_le_bytes());
+            gif.extend((height as u16).to_le_bytes());
+            gif.push(0);
+
+            let pixels = frame_indices(frame, width, height, scale, &palette, &mut indices);
+            gif.push(8);
+            for block in lzw(&pixels).chunks(255) {
+                gif.push(block.len() as u8);
+                gif.extend_from_slice(block);
+            }
+            gif.push(0);
+        }
+
+        gif.push(0x3b);
+        gif
+    }
+
+    struct Palette {
+        colors: Vec<Color>,
+    }
+
+    impl Palette {
+        fn index_of(&self, color: Color, indices: &mut HashMap<Color, u8>) -> u8 {
+            *indices.entry(color).or_insert_with(|| {
+                let distance = |other: &Color| {
+                    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+                    d(color.r, other.r) + d(color.g, other.g) + d(color.b, other.b)
+                };
+                (0..self.colors.len())
+                    .min_by_key(|&i| distance(&self.colors[i]))
+                    .unwrap_or(0) as u8
+            })
+        }
+    }
+
+    /// The first 256 distinct colors of all frames, starting with black for padding.
+    fn palette(frames: &[Grid<Cell>]) -> Palette {
+        let mut colors = vec![Color::BLACK];
+        for cell in frames.iter().flat_map(Grid::iter) {
+            let color = cell.pixel();
+            if colors.len() < 256 && !colors.contains(&color) {
+                colors.push(color);
+            }
+        }
+        Palette { colors }
+    }
+
+    fn frame_indices(
+        frame: &Grid<Cell>,
+        width: u32,
+        height: u32,
+        scale: u32,
+        palette: &Palette,
+        indices: &mut HashMap<Color, u8>,
+    ) -> Vec<u8> {
+        let mut pixels = vec![0; (width * height) as usize];
+        for (point, cell) in frame.iter_with_points() {
+            let index = palette.index_of(cell.pixel(), indices);
+            for dy in 0..scale {
+                let y = point.y as u32 * scale + dy;
+                let start = (y * width + point.x as u32 * scale) as usize;
+                pixels[start..start + scale as usize].fill(index);
+            }
+        }
+        pixels
+    }
+
+    /// Compresses 8-bit color indices with variable-length LZW codes, as the image data of a GIF.
+    pub(super) fn lzw(pixels: &[u8]) -> Vec<u8> {
+        let clear: u16 = 256;
+        let end: u16 = 257;
+
+        let mut writer = BitWriter::default();
+        let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+        let mut code_size = 9;
+        let mut last_code = end;
+
+        writer.write(clear, code_size);
+
+        let mut prefix: Option<u16> = None;
+        for &pixel in pixels {
+            let Some(current) = prefix else {
+                prefix = Some(u16::from(pixel));
+                continue;
+            };
+
+            if let Some(&code) = dictionary.get(&(current, pixel)) {
+                prefix = Some(code);
+                continue;
+            }
+
+            writer.write(current, code_size);
+            last_code += 1;
+            dictionary.insert((current, pixel), last_code);
+
+            if last_code >= 1 << code_size {
+                code_size += 1;
+            }
+            if last_code == MAX_CODE {
+                writer.write(clear, code_size);
+                dictionary.clear();
+                code_size = 9;
+                last_code = end;
+            }
+
+            prefix = Some(u16::from(pixel));
+        }
+
+        if let Some(current) = prefix {
+            writer.write(current, code_size);
+        }
+        writer.write(end, code_size);
+        writer.finish()
+    }
+
+    #[derive(Default)]
+    struct BitWriter {
+        bytes: Vec<u8>,
+        buffer: u32,
+        bits: u32,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, code: u16, size: u32) {
+            self.buffer |= u32::from(code) << self.bits;
+            self.bits += size;
+            while self.bits >= 8 {
+                self.bytes.push((self.buffer & 0xff) as u8);
+                self.buffer >>= 8;
+                self.bits -= 8;
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.bits > 0 {
+                self.bytes.push((self.buffer & 0xff) as u8);
+            }
+            self.bytes
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{gif, Recorder, MAX_FRAMES};
+    use crate::bin::util::grid::Grid;
+    use crate::bin::util::point::Point;
+    use crate::template::visualize::{Output, Visualization};
+    use crate::{day, template::PuzzleId};
+
+    fn recorder(visualization: Option<Visualization>) -> Recorder {
+        Recorder::new(PuzzleId::from(day!(14)), "test", visualization)
+    }
+
+    #[test]
+    fn only_records_when_enabled() {
+        let grid = Grid::parse_ascii("#.\n.#");
+
+        let mut disabled = recorder(None);
+        disabled.record_grid(&grid);
+        assert_eq!(disabled.frames().count(), 0);
+
+        let mut enabled = recorder(Some(Visualization::new(Output::Play, None)));
+        enabled.record_grid(&grid);
+        enabled.record_points(2, 2, &[Point::new(1, 0), Point::new(5, 5)]);
+        let frames = enabled.take_frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1][Point::new(1, 0)].glyph, '#');
+        assert_eq!(frames[1][Point::new(0, 0)].glyph, '.');
+    }
+
+    #[test]
+    fn keeps_last_frames() {
+        let mut recorder = recorder(Some(Visualization::new(Output::Gif, None)));
+        for x in 0..=MAX_FRAMES as i32 {
+            recorder.record_points(1, 1, &[Point::new(x, 0)]);
+        }
+        let frames = recorder.take_frames();
+        assert_eq!(frames.len(), MAX_FRAMES);
+        assert_eq!(frames[0][Point::new(0, 0)].glyph, '.');
+    }
+
+    #[test]
+    fn compresses_with_lzw() {
+        // clear (256), 0, 0, end (257) in 9-bit codes.
+        assert_eq!(gif::lzw(&[0, 0]), vec![0x00, 0x01, 0x00, 0x08, 0x08]);
+    }
+
+    /// Decodes GIF image data the way image viewers do.
+    /// Returns the pixels, the number of clear codes and the largest code size.
+    fn decode_lzw(bytes: &[u8]) -> (Vec<u8>, usize, u32) {
+        let (clear, end) = (256, 257);
+        let mut bytes = bytes.iter();
+        let (mut buffer, mut bits) = (0_u32, 0);
+
+        let mut dictionary: Vec<Vec<u8>> = vec![];
+        let mut code_size = 9;
+        let mut previous: Option<usize> = None;
+        let (mut pixels, mut clears, mut max_code_size) = (vec![], 0, 0);
+
+        loop {
+            while bits < code_size {
+                buffer |= u32::from(*bytes.next().expect("missing end code")) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as usize;
+            buffer >>= code_size;
+            bits -= code_size;
+            max_code_size = max_code_size.max(code_size);
+
+            if code == clear {
+                dictionary = (0..=255).map(|pixel| vec![pixel]).collect();
+                dictionary.extend([vec![], vec![]]);
+                code_size = 9;
+                previous = None;
+                clears += 1;
+                continue;
+            }
+            if code == end {
+                break;
+            }
+
+            let entry = match (dictionary.get(code), previous) {
+                (Some(entry), _) => entry.clone(),
+                // the code that is about to be added, i.e. the previous entry followed by its first pixel.
+                (None, Some(previous)) => {
+                    let mut entry = dictionary[previous].clone();
+                    entry.push(entry[0]);
+                    entry
+                }
+                (None, None) => panic!("unknown code {code}"),
+            };
+
+            if let Some(previous) = previous {
+                let mut added = dictionary[previous].clone();
+                added.push(entry[0]);
+                dictionary.push(added);
+                if dictionary.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+
+            pixels.extend(&entry);
+            previous = Some(code);
+        }
+
+        (pixels, clears, max_code_size)
+    }
+
+    #[test]
+    fn roundtrips_lzw_across_code_sizes_and_clears() {
+        // pseudo-random pixels, so that the dictionary fills up and is cleared a few times.
+        let mut state = 1_u32;
+        let pixels: Vec<u8> = (0..50_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                ((state >> 16) % 24) as u8
+            })
+            .collect();
+
+        let (decoded, clears, max_code_size) = decode_lzw(&gif::lzw(&pixels));
+        assert_eq!(decoded, pixels);
+        assert!(clears > 2, "only {clears} clear codes");
+        assert_eq!(max_code_size, 12);
+
+        // runs of a single color, which hit codes that are used right after they were added.
+        let runs: Vec<u8> = (0..5_000).map(|i| (i / 700) as u8).collect();
+        assert_eq!(decode_lzw(&gif::lzw(&runs)).0, runs);
+    }
+}
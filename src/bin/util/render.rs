@@ -0,0 +1,497 @@
+// Rendering of grids to plain text, ANSI colored terminal output and image files (PPM, PNG and SVG).
+// A style function maps every value to a cell with a glyph and colors. Overlays for paths, highlighted points and
+// bounding boxes are drawn on top, in the order they were added.
+use crate::bin::util::bounding_box::BoundingBox;
+use crate::bin::util::grid::Grid;
+use crate::bin::util::point::{Point, DOWN, LEFT, RIGHT, UP};
+use crate::template::ANSI_RESET;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0, 0, 0);
+    pub const WHITE: Color = Color::new(255, 255, 255);
+    pub const GRAY: Color = Color::new(128, 128, 128);
+    pub const RED: Color = Color::new(220, 50, 47);
+    pub const GREEN: Color = Color::new(133, 153, 0);
+    pub const BLUE: Color = Color::new(38, 139, 210);
+    pub const YELLOW: Color = Color::new(181, 137, 0);
+
+    #[must_use]
+    #[inline]
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// How a single grid cell is drawn. Images only use the colors, the background takes precedence.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cell {
+    pub glyph: char,
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+}
+
+impl Cell {
+    #[must_use]
+    #[inline]
+    pub const fn new(glyph: char) -> Self {
+        Self {
+            glyph,
+            foreground: None,
+            background: None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn colored(glyph: char, foreground: Color) -> Self {
+        Self {
+            glyph,
+            foreground: Some(foreground),
+            background: None,
+        }
+    }
+
+    /// The color of the cell's pixels in an image.
+    #[inline]
+    pub fn pixel(&self) -> Color {
+        self.background.or(self.foreground).unwrap_or(Color::BLACK)
+    }
+}
+
+enum Overlay {
+    Highlight(Vec<Point>, Color),
+    Path(Vec<Point>, Color),
+    BoundingBox(BoundingBox, Color),
+}
+
+pub struct Renderer<'a, T> {
+    grid: &'a Grid<T>,
+    style: Box<dyn Fn(&T) -> Cell + 'a>,
+    overlays: Vec<Overlay>,
+}
+
+impl<'a, T> Renderer<'a, T> {
+    #[must_use]
+    pub fn new(grid: &'a Grid<T>, style: impl Fn(&T) -> Cell + 'a) -> Self {
+        Self {
+            grid,
+            style: Box::new(style),
+            overlays: vec![],
+        }
+    }
+
+    /// Colors the background of the given points.
+    #[must_use]
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>, color: Color) -> Self {
+        self.overlays
+            .push(Overlay::Highlight(points.into_iter().collect(), color));
+        self
+    }
+
+    /// Colors the background of a path and draws arrows in the direction of each step.
+    #[must_use]
+    pub fn path(mut self, points: impl IntoIterator<Item = Point>, color: Color) -> Self {
+        self.overlays
+            .push(Overlay::Path(points.into_iter().collect(), color));
+        self
+    }
+
+    /// Colors the background of the border of a bounding box.
+    #[must_use]
+    pub fn bounding_box(mut self, bounding_box: BoundingBox, color: Color) -> Self {
+        self.overlays
+            .push(Overlay::BoundingBox(bounding_box, color));
+        self
+    }
+
+    /// Every cell of the grid with all overlays applied.
+    pub fn cells(&self) -> Grid<Cell> {
+        let mut cells = Grid::new(self.grid.width(), self.grid.height(), Cell::new(' '));
+        for (point, value) in self.grid.iter_with_points() {
+            cells[point] = (self.style)(value);
+        }
+
+        for overlay in &self.overlays {
+            match overlay {
+                Overlay::Highlight(points, color) => {
+                    for &point in points {
+                        if let Some(cell) = cells.get_mut(point) {
+                            cell.background = Some(*color);
+                        }
+                    }
+                }
+                Overlay::Path(points, color) => {
+                    for (i, &point) in points.iter().enumerate() {
+                        let glyph = points.get(i + 1).and_then(|&next| match next - point {
+                            UP => Some('^'),
+                            DOWN => Some('v'),
+                            LEFT => Some('<'),
+                            RIGHT => Some('>'),
+                            _ => None,
+                        });
+
+                        if let Some(cell) = cells.get_mut(point) {
+                            cell.background = Some(*color);
+                            if let Some(glyph) = glyph {
+                                cell.glyph = glyph;
+                            }
+                        }
+                    }
+                }
+                Overlay::BoundingBox(bounding_box, color) => {
+                    let (top_left, bottom_right) =
+                        (bounding_box.top_left, bounding_box.bottom_right);
+                    for y in top_left.y..bottom_right.y {
+                        for x in top_left.x..bottom_right.x {
+                            let on_border = x == top_left.x
+                                || y == top_left.y
+                                || x == bottom_right.x - 1
+                                || y == bottom_right.y - 1;
+
+                            if let Some(cell) =
+                                cells.get_mut(Point::new(x, y)).filter(|_| on_border)
+                            {
+                                cell.background = Some(*color);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        cells
+    }
+
+    /// The glyphs of all cells, one line per row.
+    pub fn to_text(&self) -> String {
+        let cells = self.cells();
+        let mut text = String::new();
+        for row in cells.rows() {
+            text.extend(row.iter().map(|cell| cell.glyph));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The glyphs of all cells with their colors as ANSI escape codes, one line per row.
+    pub fn to_ansi(&self) -> String {
+        let cells = self.cells();
+        let mut text = String::new();
+        for row in cells.rows() {
+            for cell in row {
+                if cell.foreground.is_none() && cell.background.is_none() {
+                    text.push(cell.glyph);
+                    continue;
+                }
+                if let Some(Color { r, g, b }) = cell.foreground {
+                    let _ = write!(text, "\x1b[38;2;{r};{g};{b}m");
+                }
+                if let Some(Color { r, g, b }) = cell.background {
+                    let _ = write!(text, "\x1b[48;2;{r};{g};{b}m");
+                }
+                text.push(cell.glyph);
+                text.push_str(ANSI_RESET);
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.to_ansi());
+    }
+
+    /// The RGB pixels of an image where every cell is a square of `scale` pixels.
+    fn pixels(&self, scale: u32) -> (u32, u32, Vec<u8>) {
+        let cells = self.cells();
+        let width = cells.width() as u32 * scale;
+        let height = cells.height() as u32 * scale;
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+
+        for row in cells.rows() {
+            for _ in 0..scale {
+                for cell in row {
+                    let Color { r, g, b } = cell.pixel();
+                    for _ in 0..scale {
+                        pixels.extend_from_slice(&[r, g, b]);
+                    }
+                }
+            }
+        }
+
+        (width, height, pixels)
+    }
+
+    /// A binary PPM image where every cell is a square of `scale` pixels.
+    pub fn to_ppm(&self, scale: u32) -> Vec<u8> {
+        let (width, height, pixels) = self.pixels(scale);
+        let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+        image.extend(pixels);
+        image
+    }
+
+    /// A PNG image where every cell is a square of `scale` pixels.
+    pub fn to_png(&self, scale: u32) -> Vec<u8> {
+        let (width, height, pixels) = self.pixels(scale);
+        png::encode(width, height, &pixels)
+    }
+
+    /// An SVG image where every cell is a square of `scale` pixels. Black cells are left out.
+    pub fn to_svg(&self, scale: u32) -> String {
+        let cells = self.cells();
+        let width = cells.width() as u32 * scale;
+        let height = cells.height() as u32 * scale;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n"
+        );
+        let _ = writeln!(
+            svg,
+            "<rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>",
+            Color::BLACK.hex()
+        );
+
+        for (point, cell) in cells.iter_with_points() {
+            let color = cell.pixel();
+            if color == Color::BLACK {
+                continue;
+            }
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{scale}\" height=\"{scale}\" fill=\"{}\"/>",
+                point.x as u32 * scale,
+                point.y as u32 * scale,
+                color.hex()
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the grid to a file, in the format of its extension: `txt`, `ansi`, `ppm`, `png` or `svg`.
+    /// Images use `scale` pixels per cell.
+    pub fn save(&self, path: impl AsRef<Path>, scale: u32) -> io::Result<()> {
+        let path = path.as_ref();
+        let contents = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("txt") => self.to_text().into_bytes(),
+            Some("ansi") => self.to_ansi().into_bytes(),
+            Some("ppm") => self.to_ppm(scale),
+            Some("png") => self.to_png(scale),
+            Some("svg") => self.to_svg(scale).into_bytes(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unsupported image format: {}", path.display()),
+                ))
+            }
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    }
+}
+
+impl Grid<u8> {
+    /// Renders the bytes of the grid as they are. Walls (`#`) are gray, other bytes except `.` are yellow.
+    #[must_use]
+    pub fn renderer(&self) -> Renderer<'_, u8> {
+        Renderer::new(self, |&value| match value {
+            b'.' => Cell::new('.'),
+            b'#' => Cell::colored('#', Color::GRAY),
+            _ => Cell::colored(value as char, Color::YELLOW),
+        })
+    }
+}
+
+impl Grid<bool> {
+    /// Renders `true` as white `#` and `false` as `.`.
+    #[must_use]
+    pub fn renderer(&self) -> Renderer<'_, bool> {
+        Renderer::new(self, |&value| {
+            if value {
+                Cell::colored('#', Color::WHITE)
+            } else {
+                Cell::new('.')
+            }
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+// Minimal PNG encoder for 8-bit RGB images. The image data is stored uncompressed, which keeps
+// the encoder small at the cost of file size.
+mod png {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    const MAX_BLOCK_SIZE: usize = 65_535;
+
+    pub fn encode(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+        let mut header = Vec::with_capacity(13);
+        header.extend(width.to_be_bytes());
+        header.extend(height.to_be_bytes());
+        // bit depth 8, color type RGB, default compression, filter and interlacing.
+        header.extend([8, 2, 0, 0, 0]);
+
+        // every row starts with its filter type, 0 for none.
+        let mut raw = Vec::with_capacity(pixels.len() + height as usize);
+        if width > 0 {
+            for row in pixels.chunks_exact(width as usize * 3) {
+                raw.push(0);
+                raw.extend_from_slice(row);
+            }
+        }
+
+        let mut png = SIGNATURE.to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        png.extend((data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend_from_slice(kind);
+        png.extend_from_slice(data);
+        let crc = crc32(&png[start..]);
+        png.extend(crc.to_be_bytes());
+    }
+
+    /// A zlib stream of uncompressed deflate blocks.
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut stream = vec![0x78, 0x01];
+        let mut blocks = data.chunks(MAX_BLOCK_SIZE).peekable();
+
+        if blocks.peek().is_none() {
+            stream.extend([1, 0, 0, 0xff, 0xff]);
+        }
+
+        while let Some(block) = blocks.next() {
+            let is_final = blocks.peek().is_none();
+            let len = block.len() as u16;
+            stream.push(u8::from(is_final));
+            stream.extend(len.to_le_bytes());
+            stream.extend((!len).to_le_bytes());
+            stream.extend_from_slice(block);
+        }
+
+        stream.extend(adler32(data).to_be_bytes());
+        stream
+    }
+
+    fn crc32(data: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &byte in data {
+            crc ^= u32::from(byte);
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xedb8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        !crc
+    }
+
+    fn adler32(data: &[u8]) -> u32 {
+        let (mut a, mut b) = (1u32, 0u32);
+        for &byte in data {
+            a = (a + u32::from(byte)) % 65_521;
+            b = (b + a) % 65_521;
+        }
+        (b << 16) | a
+    }
+
+    #[cfg(feature = "test_lib")]
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cell, Color};
+    use crate::bin::util::bounding_box::BoundingBox;
+    use crate::bin::util::grid::Grid;
+    use crate::bin::util::point::Point;
+
+    const EXAMPLE: &str = "#...\n.#..\n....";
+
+    #[test]
+    fn renders_text_with_overlays() {
+        let grid = Grid::parse_ascii(EXAMPLE);
+        let path = [Point::new(1, 0), Point::new(2, 0), Point::new(2, 1)];
+        let renderer = grid.renderer().path(path, Color::RED);
+
+        assert_eq!(renderer.to_text(), "#>v.\n.#..\n....\n");
+
+        let cells = renderer
+            .bounding_box(BoundingBox::with_dimensions(4, 3), Color::BLUE)
+            .cells();
+        assert_eq!(cells[Point::new(2, 0)].background, Some(Color::BLUE));
+        assert_eq!(cells[Point::new(2, 1)].background, Some(Color::RED));
+        assert_eq!(cells[Point::new(1, 1)].pixel(), Color::GRAY);
+    }
+
+    #[test]
+    fn renders_ansi() {
+        let grid = Grid::parse_ascii("#.");
+        assert_eq!(
+            grid.renderer().to_ansi(),
+            "\x1b[38;2;128;128;128m#\x1b[0m.\n"
+        );
+
+        let grid = Grid::new(2, 1, true);
+        let renderer = grid.renderer().highlight([Point::new(1, 0)], Color::GREEN);
+        assert_eq!(
+            renderer.cells()[Point::new(1, 0)],
+            Cell {
+                glyph: '#',
+                foreground: Some(Color::WHITE),
+                background: Some(Color::GREEN),
+            }
+        );
+    }
+
+    #[test]
+    fn renders_images() {
+        let grid = Grid::parse_ascii(EXAMPLE);
+        let renderer = grid.renderer();
+
+        let ppm = renderer.to_ppm(2);
+        assert!(ppm.starts_with(b"P6\n8 6\n255\n"));
+        assert_eq!(ppm.len(), 11 + 8 * 6 * 3);
+        assert_eq!(&ppm[11..17], &[128, 128, 128, 128, 128, 128]);
+
+        let png = renderer.to_png(2);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x08\0\0\0\x06"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+
+        let svg = renderer.to_svg(2);
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.contains("<rect x=\"2\" y=\"2\" width=\"2\" height=\"2\" fill=\"#808080\"/>"));
+    }
+}
@@ -0,0 +1,349 @@
+// Graph searches over a successor function: breadth-first search, Dijkstra and A*.
+// Nodes can be any hashable state, e.g. a `Point` or a `(Point, Point)` of position and direction.
+// Every search records all predecessors on cheapest paths, so that both a single path and all nodes
+// on any cheapest path can be reconstructed.
+use crate::bin::util::grid::Grid;
+use crate::bin::util::point::Point;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The result of [`bfs`], [`dijkstra`] or [`astar`].
+#[derive(Clone, Debug)]
+pub struct Search<N> {
+    /// The cost of the cheapest known path to every reached node. Nodes that were not expanded
+    /// before the search stopped at a goal may have a tentative cost.
+    pub distances: HashMap<N, u64>,
+    /// The nodes before every reached node on its cheapest paths. Start nodes have none.
+    pub predecessors: HashMap<N, Vec<N>>,
+    /// The goal nodes that were reached at the lowest cost, in the order they were reached.
+    pub goals: Vec<N>,
+}
+
+impl<N: Eq + Hash + Clone> Search<N> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goals: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn distance(&self, node: &N) -> Option<u64> {
+        self.distances.get(node).copied()
+    }
+
+    /// The cost of the cheapest path to a goal.
+    #[inline]
+    pub fn goal_distance(&self) -> Option<u64> {
+        self.goals.first().and_then(|goal| self.distance(goal))
+    }
+
+    /// A cheapest path from a start node to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        let mut current = node;
+        while let Some(previous) = self.predecessors.get(current).and_then(|p| p.first()) {
+            path.push(previous.clone());
+            current = previous;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// A cheapest path from a start node to the first goal that was reached.
+    #[inline]
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goals.first().and_then(|goal| self.path_to(goal))
+    }
+
+    /// All nodes on any cheapest path from a start node to one of `ends`, including the ends.
+    pub fn nodes_on_paths_to(&self, ends: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        let mut stack: Vec<N> = ends
+            .into_iter()
+            .filter(|end| self.distances.contains_key(end))
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            if let Some(predecessors) = self.predecessors.get(&node) {
+                stack.extend(
+                    predecessors
+                        .iter()
+                        .filter(|previous| !nodes.contains(*previous))
+                        .cloned(),
+                );
+            }
+            nodes.insert(node);
+        }
+
+        nodes
+    }
+
+    /// All nodes on any cheapest path from a start node to a goal.
+    #[inline]
+    pub fn nodes_on_paths(&self) -> HashSet<N> {
+        self.nodes_on_paths_to(self.goals.iter().cloned())
+    }
+}
+
+/// Breadth-first search where every step costs 1.
+/// Stops once all goals at the lowest distance are reached, or explores everything reachable if there are none.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        search.distances.insert(start.clone(), 0);
+        queue.push_back(start);
+    }
+
+    let mut best = None;
+
+    while let Some(node) = queue.pop_front() {
+        let distance = search.distances[&node];
+        if best.is_some_and(|best| distance > best) {
+            break;
+        }
+
+        if is_goal(&node) {
+            best = Some(distance);
+            search.goals.push(node);
+            continue;
+        }
+
+        for next in successors(&node) {
+            match search.distances.get(&next) {
+                Some(&known) if known == distance + 1 => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                Some(_) => {}
+                None => {
+                    search.distances.insert(next.clone(), distance + 1);
+                    search.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm over successors with the cost of the step to them, see [`astar`].
+#[inline]
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+/// A* search over successors with the cost of the step to them.
+/// The heuristic must never overestimate the remaining cost to a goal, and should be consistent for
+/// [`Search::nodes_on_paths`] to find all cheapest paths.
+/// Stops once all goals at the lowest cost are reached, or explores everything reachable if there are none.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut search = Search::new();
+    let mut heap = BinaryHeap::new();
+    let mut expanded = HashSet::new();
+
+    for start in starts {
+        search.distances.insert(start.clone(), 0);
+        heap.push(State {
+            priority: heuristic(&start),
+            node: start,
+        });
+    }
+
+    let mut best = None;
+
+    while let Some(State { priority, node }) = heap.pop() {
+        if best.is_some_and(|best| priority > best) {
+            break;
+        }
+        if !expanded.insert(node.clone()) {
+            continue;
+        }
+
+        let cost = search.distances[&node];
+        if is_goal(&node) {
+            best = Some(cost);
+            search.goals.push(node);
+            continue;
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match search.distances.get(&next) {
+                // NOTE: expanded nodes only gain predecessors through zero-cost cycles, skip them to keep paths finite.
+                Some(&known) if known == next_cost && !expanded.contains(&next) => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                Some(&known) if known <= next_cost => {}
+                _ => {
+                    search.distances.insert(next.clone(), next_cost);
+                    search.predecessors.insert(next.clone(), vec![node.clone()]);
+                    heap.push(State {
+                        priority: next_cost + heuristic(&next),
+                        node: next,
+                    });
+                }
+            }
+        }
+    }
+
+    search
+}
+
+struct State<N> {
+    priority: u64,
+    node: N,
+}
+
+impl<N> Eq for State<N> {}
+
+impl<N> PartialEq<Self> for State<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N> PartialOrd<Self> for State<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for State<N> {
+    // Reverse ordering so that the smallest element is at the top of the heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The orthogonal neighbours of a point in a grid whose value is not one of the `walls`.
+pub fn open_neighbours<'a>(
+    grid: &'a Grid<u8>,
+    walls: &'a [u8],
+) -> impl Fn(&Point) -> Vec<Point> + 'a {
+    move |&point| {
+        grid.neighbours(point)
+            .filter(|&neighbour| !walls.contains(&grid[neighbour]))
+            .collect()
+    }
+}
+
+/// Breadth-first search through a grid from `start` to `goal`, moving orthogonally around `walls`.
+pub fn bfs_grid(grid: &Grid<u8>, start: Point, goal: Point, walls: &[u8]) -> Search<Point> {
+    bfs([start], open_neighbours(grid, walls), |&point| {
+        point == goal
+    })
+}
+
+/// A* search through a grid from `start` to `goal`, moving orthogonally around `walls`.
+pub fn astar_grid(grid: &Grid<u8>, start: Point, goal: Point, walls: &[u8]) -> Search<Point> {
+    let neighbours = open_neighbours(grid, walls);
+    astar(
+        [start],
+        |point| neighbours(point).into_iter().map(|next| (next, 1)),
+        |point| u64::from(point.manhattan(goal)),
+        |&point| point == goal,
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar_grid, bfs, bfs_grid, dijkstra};
+    use crate::bin::util::grid::Grid;
+    use crate::bin::util::point::Point;
+    use std::collections::HashSet;
+
+    const MAZE: &str = "...#\n.#..\n....\n#.#.";
+
+    #[test]
+    fn finds_shortest_paths() {
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3 cost 2, 0 -> 3 costs 5.
+        let edges = |node: &u8| match node {
+            0 => vec![(1, 1), (2, 1), (3, 5)],
+            1 | 2 => vec![(3, 1)],
+            _ => vec![],
+        };
+
+        let search = dijkstra([0], edges, |&node| node == 3);
+        assert_eq!(search.goal_distance(), Some(2));
+        assert_eq!(search.path(), Some(vec![0, 1, 3]));
+        assert_eq!(search.nodes_on_paths(), HashSet::from([0, 1, 2, 3]));
+
+        let search = bfs(
+            [0],
+            |&node| edges(&node).into_iter().map(|(n, _)| n),
+            |_| false,
+        );
+        assert_eq!(search.goals, vec![]);
+        assert_eq!(search.distance(&3), Some(1));
+        assert_eq!(search.path_to(&3), Some(vec![0, 3]));
+    }
+
+    #[test]
+    fn searches_grids() {
+        let grid = Grid::parse_ascii(MAZE);
+        let start = Point::new(0, 0);
+        let goal = Point::new(3, 3);
+
+        let search = bfs_grid(&grid, start, goal, b"#");
+        assert_eq!(search.goal_distance(), Some(6));
+        assert_eq!(search.path().map(|path| path.len()), Some(7));
+        assert_eq!(search.nodes_on_paths().len(), 11);
+        assert!(!search.nodes_on_paths().contains(&Point::new(1, 3)));
+
+        let search = astar_grid(&grid, start, goal, b"#");
+        assert_eq!(search.goal_distance(), Some(6));
+        assert_eq!(search.nodes_on_paths().len(), 11);
+
+        let walled = Grid::parse_ascii("..\n##\n..");
+        let search = bfs_grid(&walled, start, Point::new(0, 2), b"#");
+        assert_eq!(search.goal_distance(), None);
+        assert_eq!(search.path(), None);
+    }
+}
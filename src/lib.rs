@@ -4,6 +4,9 @@ pub mod bin {
         pub mod bounding_box;
         pub mod grid;
         pub mod point;
+        pub mod recorder;
+        pub mod render;
+        pub mod search;
     }
 }
 
@@ -1,79 +1,241 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, stars, time, verify, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
+/// All solutions in `src/bin`, compiled into this binary so that they can run in-process.
+#[cfg(not(feature = "dhat-heap"))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+// NOTE: every solution declares its own global allocator when profiling with DHAT.
+#[cfg(feature = "dhat-heap")]
+mod solutions {
+    pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::visualize::{parse_fps, Output, Visualization};
+    use advent_of_code::template::{
+        commands::time::{Compare, ExportFormat},
+        params::{parse_param, Params},
+        Day, PuzzleId, Year,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            picks: Vec<usize>,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            return_type: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
+            alloc: bool,
             submit: Option<u8>,
+            params: Params,
+            normalize: bool,
+            visualize: Option<Visualization>,
         },
         All {
+            year: Year,
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+            in_process: bool,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            name: Option<String>,
+            compare: Option<Compare>,
+            exports: Vec<ExportFormat>,
+            alloc: bool,
+            timeout: Option<Duration>,
+            in_process: bool,
+        },
+        Verify {
+            year: Year,
+            release: bool,
+            store: bool,
+            in_process: bool,
+        },
+        Stars {
+            year: Year,
+        },
+        Watch {
+            puzzle: PuzzleId,
+            release: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Parse the `--year` option, which defaults to the year configured with `AOC_YEAR`.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::default_year))
+    }
+
+    /// Parse the day of a puzzle. Options have to be parsed first, as the day is a free argument.
+    fn parse_puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, pico_args::Error> {
+        let year = parse_year(args)?;
+        let day: Day = args.free_from_str()?;
+        Ok(PuzzleId::new(year, day))
+    }
+
+    /// Parse the `--timeout` option, given in seconds.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, pico_args::Error> {
+        args.opt_value_from_fn("--timeout", |value| {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|secs| secs.is_finite() && *secs > 0.0)
+                .map(Duration::from_secs_f64)
+                .ok_or(format!(
+                    "invalid timeout `{value}`, expected a positive number of seconds."
+                ))
+        })
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                timeout: parse_timeout(&mut args)?,
+                in_process: args.contains("--in-process"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let name = args.opt_value_from_str("--name")?;
+                let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                // NOTE: passing a baseline or threshold implies `--compare`.
+                let compare = (args.contains("--compare")
+                    || baseline.is_some()
+                    || threshold.is_some())
+                .then(|| Compare {
+                    baseline,
+                    threshold: threshold.unwrap_or(10.0),
+                });
+                let exports = args.values_from_str("--export")?;
+                let alloc = args.contains("--alloc");
+                let timeout = parse_timeout(&mut args)?;
+                let in_process = args.contains("--in-process");
+                let year = parse_year(&mut args)?;
 
                 AppArguments::Time {
+                    year,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    name,
+                    compare,
+                    exports,
+                    alloc,
+                    timeout,
+                    in_process,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            Some("verify") => AppArguments::Verify {
+                year: parse_year(&mut args)?,
+                release: args.contains("--release"),
+                store: args.contains("--store"),
+                in_process: args.contains("--in-process"),
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            Some("watch") => {
+                let release = args.contains("--release");
+
+                AppArguments::Watch {
+                    puzzle: parse_puzzle(&mut args)?,
+                    release,
+                }
+            }
+            Some("stars") => AppArguments::Stars {
+                year: parse_year(&mut args)?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+            Some("download") => AppArguments::Download {
+                puzzle: parse_puzzle(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("read") => AppArguments::Read {
+                puzzle: parse_puzzle(&mut args)?,
             },
+            Some("examples") => {
+                let picks = args.values_from_str("--pick")?;
+                let overwrite = args.contains("--overwrite");
+
+                AppArguments::Examples {
+                    puzzle: parse_puzzle(&mut args)?,
+                    picks,
+                    overwrite,
+                }
+            }
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let template = args.opt_value_from_str("--template")?;
+                let return_type = args.opt_value_from_str("--return-type")?;
+
+                AppArguments::Scaffold {
+                    puzzle: parse_puzzle(&mut args)?,
+                    download,
+                    overwrite,
+                    template,
+                    return_type,
+                }
+            }
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let params = args.values_from_fn("--param", parse_param)?;
+                let dhat = args.contains("--dhat");
+                let alloc = args.contains("--alloc");
+                let normalize = args.contains("--normalize");
+                let visualize: Option<Output> = args.opt_value_from_str("--visualize")?;
+                let fps = args.opt_value_from_fn("--fps", parse_fps)?;
+
+                AppArguments::Solve {
+                    puzzle: parse_puzzle(&mut args)?,
+                    release,
+                    submit,
+                    dhat,
+                    alloc,
+                    params: params.into_iter().collect(),
+                    normalize,
+                    visualize: visualize.map(|output| Visualization::new(output, fps)),
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -102,33 +264,99 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                timeout,
+                in_process,
+            } => all::handle(
+                year,
+                release,
+                jobs,
+                timeout,
+                in_process.then_some(solutions::SOLUTIONS),
+            ),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                name,
+                compare,
+                exports,
+                alloc,
+                timeout,
+                in_process,
+            } => time::handle(
+                year,
                 day,
+                all,
+                store,
+                name,
+                compare,
+                &exports,
+                alloc,
+                timeout,
+                in_process.then_some(solutions::SOLUTIONS),
+            ),
+            AppArguments::Verify {
+                year,
+                release,
+                store,
+                in_process,
+            } => verify::handle(
+                year,
+                release,
+                store,
+                in_process.then_some(solutions::SOLUTIONS),
+            ),
+            AppArguments::Stars { year } => stars::handle(year),
+            AppArguments::Watch { puzzle, release } => watch::handle(puzzle, release),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples {
+                puzzle,
+                picks,
+                overwrite,
+            } => examples::handle(puzzle, &picks, overwrite),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
+                template,
+                return_type,
             } => {
-                scaffold::handle(day, overwrite);
+                // NOTE: download first, so that the template can use the puzzle description.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
+                scaffold::handle(
+                    puzzle,
+                    overwrite,
+                    template.as_deref(),
+                    return_type.as_deref(),
+                );
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
+                alloc,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                params,
+                normalize,
+                visualize,
+            } => solve::handle(
+                puzzle, release, dhat, alloc, submit, &params, normalize, visualize,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        download::handle(puzzle);
+                        scaffold::handle(puzzle, false, None, None);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,160 @@
+//! Heap allocation statistics, collected by a counting global allocator.
+//!
+//! The allocator is only installed with the `alloc-stats` feature (`cargo solve <day> --alloc`, `cargo time --alloc`).
+//! It forwards to the system allocator and counts the allocations made while a part runs, see [`measure`].
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(all(feature = "alloc-stats", feature = "dhat-heap"))]
+compile_error!("the features `alloc-stats` and `dhat-heap` both install a global allocator and can not be combined.");
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that counts allocations and keeps track of the current and peak heap size.
+pub struct CountingAlloc;
+
+fn record_alloc(size: usize) {
+    let size = size as u64;
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    CURRENT_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    // NOTE: a reallocation counts as a new allocation of the new size.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The heap allocations of a single run of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Maximum number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+}
+
+/// Whether allocation statistics are collected, i.e. the `alloc-stats` feature is enabled.
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Runs `f` and measures its heap allocations. Without the `alloc-stats` feature, only runs `f`.
+///
+/// Allocations of other threads that happen at the same time are counted as well.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let baseline = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(baseline, Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline),
+    };
+
+    (result, Some(stats))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn measures_allocations() {
+        let (vec, stats) = super::measure(|| {
+            let mut vec: Vec<u64> = Vec::with_capacity(16);
+            vec.push(1);
+            vec
+        });
+        let stats = stats.unwrap();
+        assert_eq!(vec, [1]);
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes >= 128);
+        assert!(stats.peak_bytes >= 128);
+    }
+}
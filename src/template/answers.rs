@@ -0,0 +1,364 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, Year};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the known answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub year: Year,
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answer {
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year, self.day)
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of comparing a computed answer with the stored one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    Missing,
+}
+
+/// Represents the known answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// Fails if the file can not be read or parsed, so that a broken store is never mistaken for an empty one.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(s) => Answers::try_from(s)
+                .map_err(|e| format!("could not parse \"{ANSWERS_FILE_PATH}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("could not read \"{ANSWERS_FILE_PATH}\": {e}")),
+        }
+    }
+
+    /// Merge two sets of answers, overwriting parts of `self` with parts of `other` if present.
+    pub fn merge(&self, new: &Self) -> Self {
+        let mut data = self.data.clone();
+
+        for answer in &new.data {
+            match data.iter_mut().find(|a| a.puzzle() == answer.puzzle()) {
+                Some(existing) => {
+                    if answer.part_1.is_some() {
+                        existing.part_1.clone_from(&answer.part_1);
+                    }
+                    if answer.part_2.is_some() {
+                        existing.part_2.clone_from(&answer.part_2);
+                    }
+                }
+                None => data.push(answer.clone()),
+            }
+        }
+
+        data.sort_unstable_by_key(Answer::puzzle);
+        Answers { data }
+    }
+
+    pub fn get(&self, puzzle: PuzzleId, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.puzzle() == puzzle)
+            .and_then(|a| a.part(part))
+    }
+
+    /// Compare a computed answer with the stored answer for a puzzle and part.
+    pub fn verify(&self, puzzle: PuzzleId, part: u8, actual: Option<&str>) -> Verdict {
+        match self.get(puzzle, part) {
+            None => Verdict::Missing,
+            Some(expected) if actual == Some(expected) => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.map(ToString::to_string),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("year".into(), JsonValue::String(value.year.to_string()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        // NOTE: answers stored by older versions do not have a year and belong to the default year.
+        let year = match json.get("year") {
+            Some(v) => v.get::<String>().and_then(|year| Year::from_str(year).ok()),
+            None => Year::from_env(),
+        }
+        .ok_or("Expected answer.year to be a Year struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            year,
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{day, year};
+
+    use super::{Answer, Answers, Verdict};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    year: year!(2024),
+                    day: day!(1),
+                    part_1: Some("11".into()),
+                    part_2: Some("31".into()),
+                },
+                Answer {
+                    year: year!(2024),
+                    day: day!(3),
+                    part_1: Some("161".into()),
+                    part_2: None,
+                },
+            ],
+        }
+    }
+
+    mod deserialization {
+        use crate::{day, template::answers::Answers};
+
+        #[test]
+        fn handles_json_answers() {
+            let json =
+                r#"{ "data": [{ "year": "2024", "day": "01", "part_1": "11", "part_2": null }] }"#
+                    .to_string();
+            let answers = Answers::try_from(json).unwrap();
+            assert_eq!(answers.data.len(), 1);
+            let answer = answers.data.first().unwrap();
+            assert_eq!(answer.day, day!(1));
+            assert_eq!(answer.part_1, Some("11".to_string()));
+            assert_eq!(answer.part_2, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_answers() {
+            let json =
+                r#"{ "data": [{ "year": "2024", "day": "26", "part_1": null, "part_2": null }] }"#
+                    .to_string();
+            Answers::try_from(json).unwrap();
+        }
+    }
+
+    mod serialization {
+        use super::get_mock_answers;
+        use crate::template::answers::Answers;
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn roundtrips_answers() {
+            let answers = get_mock_answers();
+            let json = JsonValue::from(answers.clone()).stringify().unwrap();
+            assert_eq!(Answers::try_from(json).unwrap().data, answers.data);
+        }
+    }
+
+    mod verify {
+        use super::{get_mock_answers, Verdict};
+        use crate::{day, template::PuzzleId, year};
+
+        #[test]
+        fn passes_matching_answers() {
+            let answers = get_mock_answers();
+            assert_eq!(
+                answers.verify(PuzzleId::new(year!(2024), day!(1)), 1, Some("11")),
+                Verdict::Pass
+            );
+        }
+
+        #[test]
+        fn fails_mismatching_answers() {
+            let answers = get_mock_answers();
+            assert_eq!(
+                answers.verify(PuzzleId::new(year!(2024), day!(1)), 2, Some("32")),
+                Verdict::Fail {
+                    expected: "31".into(),
+                    actual: Some("32".into())
+                }
+            );
+            assert_eq!(
+                answers.verify(PuzzleId::new(year!(2024), day!(1)), 2, None),
+                Verdict::Fail {
+                    expected: "31".into(),
+                    actual: None
+                }
+            );
+        }
+
+        #[test]
+        fn reports_missing_answers() {
+            let answers = get_mock_answers();
+            assert_eq!(
+                answers.verify(PuzzleId::new(year!(2024), day!(3)), 2, Some("48")),
+                Verdict::Missing
+            );
+            assert_eq!(
+                answers.verify(PuzzleId::new(year!(2024), day!(2)), 1, Some("2")),
+                Verdict::Missing
+            );
+        }
+    }
+
+    mod merge {
+        use super::{get_mock_answers, Answer, Answers};
+        use crate::{day, template::PuzzleId, year};
+
+        #[test]
+        fn merges_parts() {
+            let answers = get_mock_answers();
+            let other = Answers {
+                data: vec![
+                    Answer {
+                        year: year!(2024),
+                        day: day!(3),
+                        part_1: None,
+                        part_2: Some("48".into()),
+                    },
+                    Answer {
+                        year: year!(2024),
+                        day: day!(2),
+                        part_1: Some("2".into()),
+                        part_2: None,
+                    },
+                ],
+            };
+            let merged = answers.merge(&other);
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(
+                merged.get(PuzzleId::new(year!(2024), day!(3)), 1),
+                Some("161")
+            );
+            assert_eq!(
+                merged.get(PuzzleId::new(year!(2024), day!(3)), 2),
+                Some("48")
+            );
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Minimal HTML to markdown conversion for the puzzle pages of Advent of Code.
+//!
+//! Only the handful of elements used in puzzle descriptions are translated (headings, paragraphs, emphasis, code,
+//! code blocks, links and lists), all other tags are dropped while keeping their text.
+
+#[derive(Default)]
+struct Converter {
+    out: String,
+    in_pre: bool,
+    list_depth: usize,
+    links: Vec<Option<String>>,
+}
+
+impl Converter {
+    fn open(&mut self, name: &str, attributes: &str) {
+        match name {
+            "h1" | "h2" | "h3" => {
+                self.block();
+                self.out.push_str("## ");
+            }
+            "p" => self.block(),
+            "em" | "strong" | "b" | "i" if !self.in_pre => self.out.push('*'),
+            "code" if !self.in_pre => self.out.push('`'),
+            "pre" => {
+                self.block();
+                self.out.push_str("```\n");
+                self.in_pre = true;
+            }
+            "ul" | "ol" => {
+                if self.list_depth == 0 {
+                    self.block();
+                } else {
+                    self.line();
+                }
+                self.list_depth += 1;
+            }
+            "li" => {
+                self.line();
+                self.out
+                    .push_str(&"  ".repeat(self.list_depth.saturating_sub(1)));
+                self.out.push_str("- ");
+            }
+            "a" => {
+                let href = attribute(attributes, "href");
+                if href.is_some() {
+                    self.out.push('[');
+                }
+                self.links.push(href);
+            }
+            "br" => self.out.push('\n'),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "p" => self.block(),
+            "em" | "strong" | "b" | "i" if !self.in_pre => self.out.push('*'),
+            "code" if !self.in_pre => self.out.push('`'),
+            "pre" => {
+                self.line();
+                self.out.push_str("```");
+                self.in_pre = false;
+                self.block();
+            }
+            "ul" | "ol" => {
+                self.list_depth = self.list_depth.saturating_sub(1);
+                if self.list_depth == 0 {
+                    self.block();
+                }
+            }
+            "a" => {
+                if let Some(Some(href)) = self.links.pop() {
+                    self.out.push_str(&format!("]({href})"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+
+        if self.in_pre {
+            self.out.push_str(&text);
+        } else if !(text.trim().is_empty() && text.contains('\n')) {
+            // NOTE: whitespace between block elements is formatting of the HTML source, not content.
+            self.out.push_str(&text.replace('\n', " "));
+        }
+    }
+
+    /// Ends the current line, if any.
+    fn line(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    /// Ends the current block with an empty line, if any.
+    fn block(&mut self) {
+        self.line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+}
+
+/// Reads the value of an attribute from the attributes of a tag, e.g. `href="/2024"`.
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = attributes[start..].find('"')?;
+    Some(decode_entities(&attributes[start..start + len]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#')?.parse().ok())
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Converts a fragment of a puzzle page to markdown.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut converter = Converter::default();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        converter.text(&rest[..start]);
+
+        let Some(end) = rest[start..].find('>').map(|end| start + end) else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            converter.close(&name.trim().to_lowercase());
+        } else if !tag.starts_with('!') {
+            let tag = tag.trim_end_matches('/');
+            let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            converter.open(&name.to_lowercase(), attributes);
+        }
+    }
+
+    converter.text(rest);
+
+    let out = converter.out.trim();
+    if out.is_empty() {
+        String::new()
+    } else {
+        format!("{out}\n")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, html_to_markdown};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt; b &amp;&amp; c &gt; d"),
+            "a < b && c > d"
+        );
+        assert_eq!(decode_entities("&#39;&#x41;&unknown; &"), "'A&unknown; &");
+    }
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is missing. See <a href="/2024/about">about</a>.</p>
+<ul>
+<li>Pair up <code>3</code> and <code>4</code>.</li>
+<li>Then <code>x &lt; y</code>.</li>
+</ul>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>In this example, the total is <code><em>11</em></code>.</p>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is missing. See [about](/2024/about).
+
+- Pair up `3` and `4`.
+- Then `x < y`.
+
+```
+3   4
+4   3
+```
+
+In this example, the total is `*11*`.
+"
+        );
+    }
+
+    #[test]
+    fn keeps_code_blocks_verbatim() {
+        assert_eq!(
+            html_to_markdown("<pre><code>1 <em>2</em>\n3</code></pre>"),
+            "```\n1 2\n3\n```\n"
+        );
+    }
+}
@@ -0,0 +1,404 @@
+/// Native client for the Advent of Code website.
+///
+/// # Configuration
+/// The session token is read from the `AOC_SESSION` environment variable. If it is not set, it is read from the file
+/// at `AOC_SESSION_FILE`, which defaults to `<home_directory>/.adventofcode.session`.
+/// The base URL defaults to `https://adventofcode.com` and can be changed with `AOC_BASE_URL`, e.g. to run against a
+/// local stand-in server.
+/// Requests identify themselves with the `AOC_USER_AGENT` environment variable, which should point to your repository.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use regex::Regex;
+
+use crate::template::PuzzleId;
+
+mod markdown;
+
+pub use markdown::html_to_markdown;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    SessionRejected,
+    PuzzleNotFound,
+    BadStatus(u16),
+    Transport(String),
+    IO(std::io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session token found. Set `AOC_SESSION` or create the file `~/{SESSION_FILE_NAME}`."
+            ),
+            AocClientError::SessionRejected => {
+                write!(f, "the session token was rejected, it might have expired.")
+            }
+            AocClientError::PuzzleNotFound => {
+                write!(f, "the puzzle does not exist or is not unlocked yet.")
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "the request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for AocClientError {
+    fn from(e: std::io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(400, _) => AocClientError::SessionRejected,
+            ureq::Error::Status(404, _) => AocClientError::PuzzleNotFound,
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// An answer was submitted too recently, the server asks to wait before submitting again.
+    Wait(Option<Duration>),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+/// The response to a submitted answer.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub outcome: Outcome,
+    /// The message of the server, converted to markdown.
+    pub message: String,
+}
+
+impl Submission {
+    fn from_html(html: &str) -> Self {
+        let message = articles_to_markdown(html);
+
+        let outcome = if message.contains("That's the right answer") {
+            Outcome::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Outcome::TooHigh
+            } else if message.contains("too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Incorrect
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Outcome::Wait(parse_wait(&message))
+        } else if message.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown
+        };
+
+        Submission { outcome, message }
+    }
+}
+
+/// Parses the remaining wait time of a message like "You have 4m 52s left to wait.".
+fn parse_wait(message: &str) -> Option<Duration> {
+    let re = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    let captures = re.captures(message)?;
+    let minutes: u64 = captures
+        .get(1)
+        .map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let seconds: u64 = captures[2].parse().ok()?;
+    Some(Duration::from_secs(minutes * 60 + seconds))
+}
+
+/// Converts the `<article>` elements of a page to markdown.
+fn articles_to_markdown(html: &str) -> String {
+    let re = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    re.captures_iter(html)
+        .map(|captures| html_to_markdown(&captures[1]))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, user_agent: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(user_agent).build(),
+        }
+    }
+
+    /// Creates a client from the environment, see [module docs](self).
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let user_agent = env::var("AOC_USER_AGENT")
+            .ok()
+            .filter(|agent| !agent.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.into());
+        Ok(Client::new(&base_url, &get_session()?, &user_agent))
+    }
+
+    /// Fetches the puzzle input.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.puzzle_url(puzzle)))
+    }
+
+    /// Fetches the puzzle description, converted to markdown.
+    /// The description of the second part is only included once the first part is solved.
+    pub fn description(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.puzzle_url(puzzle))?;
+        Ok(articles_to_markdown(&html))
+    }
+
+    /// Submits the answer of a puzzle part.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, AocClientError> {
+        let html = self
+            .agent
+            .post(&format!("{}/answer", self.puzzle_url(puzzle)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+        Ok(Submission::from_html(&html))
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+}
+
+fn get_session_file() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("AOC_SESSION_FILE") {
+        return Some(PathBuf::from(path));
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| Path::new(&home).join(SESSION_FILE_NAME))
+}
+
+fn get_session() -> Result<String, AocClientError> {
+    let session = env::var("AOC_SESSION")
+        .ok()
+        .or_else(|| get_session_file().and_then(|path| fs::read_to_string(path).ok()));
+
+    // NOTE: accept the cookie as copied from the browser, including the `session=` prefix.
+    session
+        .map(|s| s.trim().trim_start_matches("session=").to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or(AocClientError::SessionNotFound)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Checks that a session token is configured.
+pub fn check() -> Result<(), AocClientError> {
+    get_session().map(|_| ())
+}
+
+/// Fetches the puzzle description, stores it in `data/{year}/puzzles` and prints it.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let description = Client::from_env()?.description(puzzle)?;
+    write_data_file(&puzzle.data_path("puzzles", "md"), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+/// Fetches the puzzle input and description, and stores them in `data/{year}`.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = Client::from_env()?;
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    write_data_file(&input_path, &client.input(puzzle)?)?;
+    write_data_file(&puzzle_path, &client.description(puzzle)?)?;
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<Submission, AocClientError> {
+    Client::from_env()?.submit(puzzle, part, result)
+}
+
+fn write_data_file(path: &Path, contents: &str) -> Result<(), std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{AocClientError, Client, Outcome, Submission, DEFAULT_USER_AGENT};
+    use crate::{day, template::PuzzleId, year};
+
+    /// Serves a single response from a local stand-in server, returns its URL and the received request.
+    fn serve_once(status: &str, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let status = status.to_string();
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                request.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (url, server) = serve_once("200 OK", "1 2\n3 4\n");
+        let client = Client::new(&url, "abc", DEFAULT_USER_AGENT);
+        let input = client.input(PuzzleId::new(year!(2023), day!(5))).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1 2\n3 4\n");
+        assert!(request.starts_with("GET /2023/day/5/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=abc"));
+    }
+
+    #[test]
+    fn converts_descriptions() {
+        let (url, server) = serve_once(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 5 ---</h2><p>Hi <em>there</em>.</p></article></main>",
+        );
+        let client = Client::new(&url, "abc", DEFAULT_USER_AGENT);
+        let description = client
+            .description(PuzzleId::new(year!(2023), day!(5)))
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(description, "## --- Day 5 ---\n\nHi *there*.\n");
+    }
+
+    #[test]
+    fn maps_error_statuses() {
+        let (url, server) = serve_once("404 Not Found", "");
+        let client = Client::new(&url, "abc", DEFAULT_USER_AGENT);
+        let result = client.input(PuzzleId::new(year!(2023), day!(5)));
+        server.join().unwrap();
+
+        assert!(matches!(result, Err(AocClientError::PuzzleNotFound)));
+    }
+
+    #[test]
+    fn classifies_submissions() {
+        let outcome = |message: &str| {
+            Submission::from_html(&format!("<main><article><p>{message}</p></article></main>"))
+                .outcome
+        };
+
+        assert_eq!(
+            outcome("That's the right answer! You are one gold star closer."),
+            Outcome::Correct
+        );
+        assert_eq!(
+            outcome("That's not the right answer; your answer is too high."),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            outcome("That's not the right answer; your answer is too low."),
+            Outcome::TooLow
+        );
+        assert_eq!(outcome("That's not the right answer."), Outcome::Incorrect);
+        assert_eq!(
+            outcome("You gave an answer too recently. You have 1m 5s left to wait."),
+            Outcome::Wait(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            outcome("You don't seem to be solving the right level."),
+            Outcome::WrongLevel
+        );
+    }
+}
@@ -0,0 +1,362 @@
+/// Exports benchmark timings as CSV, as JSON with raw nanoseconds, or as a self-contained HTML page with an SVG bar chart.
+/// Used by `cargo time --export <format>`.
+use std::{collections::HashMap, fmt::Write, fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::report::stats_to_json;
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::Year;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "html" => Ok(ExportFormat::Html),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected `csv`, `json` or `html`."
+            )),
+        }
+    }
+}
+
+/// Writes the timings of a year to `data/<year>/benchmarks.<extension>` and returns the path.
+pub fn export(timings: &Timings, year: Year, format: ExportFormat) -> io::Result<PathBuf> {
+    let content = match format {
+        ExportFormat::Csv => to_csv(timings),
+        ExportFormat::Json => to_json(timings),
+        ExportFormat::Html => to_html(timings, year),
+    };
+
+    let path = PathBuf::from(format!("data/{year}/benchmarks.{}", format.extension()));
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, content)?;
+    Ok(path)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The raw nanoseconds of a part. Prefers the mean of the benchmark statistics, as the stored
+/// duration is rounded, and falls back to parsing the stored duration for timings without statistics.
+fn part_nanos(timing: Option<&PartTiming>) -> Option<f64> {
+    let timing = timing?;
+    #[allow(clippy::cast_precision_loss)]
+    timing
+        .stats
+        .map(|stats| stats.mean.as_nanos() as f64)
+        .or_else(|| parse_duration_nanos(&timing.duration))
+}
+
+/// Parses a duration formatted with `{:?}`, e.g. `74.1µs`, as nanoseconds.
+fn parse_duration_nanos(duration: &str) -> Option<f64> {
+    let split = duration.find(|c: char| c.is_alphabetic())?;
+    let (value, unit) = duration.split_at(split);
+
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" | "us" => 1_000.0,
+        "ms" => 1_000_000.0,
+        "s" => 1_000_000_000.0,
+        _ => return None,
+    };
+
+    value.trim().parse::<f64>().ok().map(|value| value * factor)
+}
+
+/// The parse step, part 1 and part 2 of a timing as raw nanoseconds.
+fn nanos(timing: &Timing) -> [Option<f64>; 3] {
+    timing.parts().map(part_nanos)
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn to_csv(timings: &Timings) -> String {
+    let mut csv = String::from("year,day,parse_nanos,part_1_nanos,part_2_nanos,total_nanos\n");
+
+    for timing in &timings.data {
+        let cells: Vec<String> = nanos(timing)
+            .iter()
+            .map(|nanos| nanos.map(|n| format!("{n:.0}")).unwrap_or_default())
+            .collect();
+
+        writeln!(
+            csv,
+            "{},{},{},{:.0}",
+            timing.year,
+            timing.day.into_inner(),
+            cells.join(","),
+            timing.total_nanos
+        )
+        .unwrap();
+    }
+
+    csv
+}
+
+pub fn to_json(timings: &Timings) -> String {
+    let optional = |value: Option<f64>| value.map_or(JsonValue::Null, JsonValue::Number);
+
+    let days = timings
+        .data
+        .iter()
+        .map(|timing| {
+            let [parse, part_1, part_2] = nanos(timing);
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+            map.insert(
+                "year".into(),
+                JsonValue::Number(f64::from(timing.year.into_inner())),
+            );
+            map.insert(
+                "day".into(),
+                JsonValue::Number(f64::from(timing.day.into_inner())),
+            );
+            map.insert("parse_nanos".into(), optional(parse));
+            map.insert("part_1_nanos".into(), optional(part_1));
+            map.insert("part_2_nanos".into(), optional(part_2));
+            map.insert("total_nanos".into(), JsonValue::Number(timing.total_nanos));
+            for (key, part) in ["parse_stats", "part_1_stats", "part_2_stats"]
+                .into_iter()
+                .zip(timing.parts())
+            {
+                map.insert(key.into(), stats_to_json(part.and_then(|p| p.stats)));
+            }
+
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("days".into(), JsonValue::Array(days));
+    map.insert(
+        "total_nanos".into(),
+        JsonValue::Number(timings.data.iter().map(|t| t.total_nanos).sum()),
+    );
+
+    JsonValue::Object(map).format().unwrap()
+}
+
+/* -------------------------------------------------------------------------- */
+
+const CHART_WIDTH: f64 = 640.0;
+const LABEL_WIDTH: f64 = 80.0;
+const VALUE_WIDTH: f64 = 100.0;
+const ROW_HEIGHT: f64 = 24.0;
+const LEGEND_HEIGHT: f64 = 32.0;
+
+/// Colors of the parse step, part 1 and part 2.
+const COLORS: [&str; 3] = ["#9e9e9e", "#4e79a7", "#f28e2b"];
+const LEGEND: [&str; 3] = ["Parse", "Part 1", "Part 2"];
+
+/// A self-contained HTML page with a stacked bar chart of the total time of every day.
+#[allow(clippy::cast_precision_loss)]
+pub fn to_html(timings: &Timings, year: Year) -> String {
+    // NOTE: the bars are stacked, so the widest bar is the largest sum of the parts of a day.
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| nanos(t).iter().flatten().sum::<f64>().max(t.total_nanos))
+        .fold(0.0, f64::max);
+
+    let width = LABEL_WIDTH + CHART_WIDTH + VALUE_WIDTH;
+    let height = LEGEND_HEIGHT + ROW_HEIGHT * timings.data.len() as f64;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="sans-serif" font-size="12">"#
+    );
+
+    for (i, (color, label)) in COLORS.iter().zip(LEGEND).enumerate() {
+        let x = LABEL_WIDTH + i as f64 * 90.0;
+        write!(
+            svg,
+            r#"<rect x="{x}" y="8" width="12" height="12" fill="{color}"/><text x="{}" y="18">{label}</text>"#,
+            x + 16.0
+        )
+        .unwrap();
+    }
+
+    for (row, timing) in timings.data.iter().enumerate() {
+        let y = LEGEND_HEIGHT + row as f64 * ROW_HEIGHT;
+        let text_y = y + ROW_HEIGHT / 2.0 + 4.0;
+
+        write!(
+            svg,
+            r#"<text x="{}" y="{text_y}" text-anchor="end">Day {}</text>"#,
+            LABEL_WIDTH - 8.0,
+            timing.day.into_inner()
+        )
+        .unwrap();
+
+        let mut x = LABEL_WIDTH;
+        for (nanos, color) in nanos(timing).iter().zip(COLORS) {
+            let Some(nanos) = nanos else { continue };
+            let bar_width = if max_nanos > 0.0 {
+                nanos / max_nanos * CHART_WIDTH
+            } else {
+                0.0
+            };
+
+            write!(
+                svg,
+                r#"<rect x="{x:.2}" y="{:.2}" width="{bar_width:.2}" height="{:.2}" fill="{color}"><title>{:.3}ms</title></rect>"#,
+                y + 4.0,
+                ROW_HEIGHT - 8.0,
+                nanos / 1_000_000_f64
+            )
+            .unwrap();
+            x += bar_width;
+        }
+
+        write!(
+            svg,
+            r#"<text x="{:.2}" y="{text_y}">{:.3}ms</text>"#,
+            x + 6.0,
+            timing.total_nanos / 1_000_000_f64
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code {year} Benchmarks</title>
+</head>
+<body style="font-family: sans-serif">
+<h1>Advent of Code {year} Benchmarks</h1>
+<p>Total: {:.2}ms</p>
+{svg}
+</body>
+</html>
+"#,
+        timings.total_millis()
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_duration_nanos, to_csv, to_html, to_json, ExportFormat};
+    use crate::{
+        day,
+        template::{
+            runner::BenchStats,
+            timings::{PartTiming, Timing, Timings},
+            PuzzleId,
+        },
+        year,
+    };
+    use std::{collections::HashMap, str::FromStr, time::Duration};
+    use tinyjson::JsonValue;
+
+    fn get_mock_timings() -> Timings {
+        let timing = |day, part_1: &str, part_2: Option<&str>, total_nanos| Timing {
+            part_1: Some(PartTiming::new(part_1)),
+            part_2: part_2.map(PartTiming::new),
+            total_nanos,
+            ..Timing::new(PuzzleId::new(year!(2024), day))
+        };
+
+        let mut timings = Timings {
+            data: vec![
+                timing(day!(1), "10ms", Some("20ms"), 3e7),
+                timing(day!(2), "74.1µs", None, 74_100.0),
+            ],
+        };
+
+        timings.data[0].part_1.as_mut().unwrap().stats = Some(BenchStats {
+            mean: Duration::from_nanos(10_000_123),
+            min: Duration::from_millis(9),
+            median: Duration::from_millis(10),
+            p95: Duration::from_millis(11),
+            stddev: Duration::from_micros(500),
+            samples: 100,
+            outliers: 0,
+        });
+
+        timings
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(ExportFormat::from_str("CSV"), Ok(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_str("html"), Ok(ExportFormat::Html));
+        assert!(ExportFormat::from_str("xml").is_err());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration_nanos("39.0ns"), Some(39.0));
+        assert_eq!(parse_duration_nanos("74.1µs"), Some(74_100.0));
+        assert_eq!(parse_duration_nanos("1.5ms"), Some(1_500_000.0));
+        assert_eq!(parse_duration_nanos("2s"), Some(2_000_000_000.0));
+        assert_eq!(parse_duration_nanos("-"), None);
+    }
+
+    #[test]
+    fn exports_csv() {
+        assert_eq!(
+            to_csv(&get_mock_timings()),
+            [
+                "year,day,parse_nanos,part_1_nanos,part_2_nanos,total_nanos",
+                "2024,1,,10000123,20000000,30000000",
+                "2024,2,,74100,,74100",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn exports_json() {
+        let json: JsonValue = to_json(&get_mock_timings()).parse().unwrap();
+        let json = json.get::<HashMap<String, JsonValue>>().unwrap();
+        let days = json["days"].get::<Vec<JsonValue>>().unwrap();
+        let day = days[0].get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(day["part_1_nanos"], JsonValue::Number(10_000_123.0));
+        assert_eq!(day["part_2_nanos"], JsonValue::Number(20_000_000.0));
+        assert!(day["parse_nanos"].is_null());
+        assert!(day["part_1_stats"]
+            .get::<HashMap<String, JsonValue>>()
+            .is_some());
+        assert_eq!(json["total_nanos"], JsonValue::Number(30_074_100.0));
+    }
+
+    #[test]
+    fn exports_html() {
+        let html = to_html(&get_mock_timings(), year!(2024));
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Advent of Code 2024 Benchmarks</title>"));
+        assert!(html.contains("<svg"));
+        assert!(html.contains(">Day 1</text>"));
+        assert!(html.contains(">Day 2</text>"));
+        assert!(html.contains("30.000ms"));
+    }
+}
@@ -1,5 +1,25 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    registry::Solution,
+    run_multi::{run_multi, Executor},
+    Year,
+};
+
+/// Run all days of a year. Passing a registry runs the days in-process instead of spawning their solution binaries.
+/// Days that run longer than `timeout` are stopped.
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    registry: Option<&[Solution]>,
+) {
+    run_multi(
+        year,
+        &all_days().collect(),
+        false,
+        Executor::new(is_release, jobs, registry).with_timeout(timeout),
+    );
 }
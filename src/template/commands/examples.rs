@@ -0,0 +1,252 @@
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::PathBuf,
+    process,
+};
+
+use regex::Regex;
+
+use super::scaffold::format_example;
+use crate::template::{puzzle::Description, PuzzleId};
+
+/// Picks the first example that spans multiple lines, as puzzles tend to quote single-line values in code blocks.
+fn default_pick(candidates: &[String]) -> usize {
+    candidates
+        .iter()
+        .position(|code| code.lines().count() > 1)
+        .unwrap_or(0)
+        + 1
+}
+
+fn print_candidates(candidates: &[String]) {
+    for (i, code) in candidates.iter().enumerate() {
+        let lines: Vec<&str> = code.lines().collect();
+        println!("[{}] ({} lines)", i + 1, lines.len());
+        for line in lines.iter().take(3) {
+            println!("    {line}");
+        }
+        if lines.len() > 3 {
+            println!("    ...");
+        }
+    }
+}
+
+fn prompt_picks(default: usize) -> Vec<usize> {
+    print!("Pick the example(s) to store, e.g. `1` or `1,3` [{default}]: ");
+    io::stdout().flush().unwrap();
+
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line).unwrap();
+
+    let picks: Vec<usize> = line
+        .split([',', ' '])
+        .filter_map(|pick| pick.trim().parse().ok())
+        .collect();
+
+    if picks.is_empty() {
+        vec![default]
+    } else {
+        picks
+    }
+}
+
+/// The path of the n-th example of a puzzle, e.g. `01.txt`, `01-ex2.txt`.
+/// The `-ex` suffix keeps additional examples apart from the part files of [`read_file_part`](crate::template::read_file_part).
+fn example_path(puzzle: PuzzleId, n: usize) -> PathBuf {
+    if n == 1 {
+        puzzle.data_path("examples", "txt")
+    } else {
+        puzzle.data_path_with_suffix("examples", &format!("-ex{n}"), "txt")
+    }
+}
+
+/// Replaces the expected result `None` in the test of a part with the example's answer.
+/// Returns `None` if the test was not found or already has an expected result.
+fn fill_expected(source: &str, part: u8, answer: &str) -> Option<String> {
+    let name = if part == 1 { "part_one" } else { "part_two" };
+
+    let return_type = Regex::new(&format!(r"pub fn {name}\(.*\) -> Option<(.+)>"))
+        .unwrap()
+        .captures(source)
+        .map_or_else(|| "u64".to_string(), |captures| captures[1].to_string());
+
+    let expected = format_example(Some(answer), &return_type);
+    if expected == "None" {
+        return None;
+    }
+
+    let test_start = source.find(&format!("fn test_{name}()"))?;
+    let test_end = source[test_start + 1..]
+        .find("#[test]")
+        .map_or(source.len(), |end| test_start + 1 + end);
+
+    let assertion = "assert_eq!(result, None);";
+    let offset = source[test_start..test_end].find(assertion)? + test_start;
+
+    Some(format!(
+        "{}assert_eq!(result, {expected});{}",
+        &source[..offset],
+        &source[offset + assertion.len()..]
+    ))
+}
+
+pub fn handle(puzzle: PuzzleId, picks: &[usize], overwrite: bool) {
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let Some(description) = Description::read(puzzle) else {
+        eprintln!(
+            "Puzzle description \"{}\" not found. Download it with `cargo download {}` first.",
+            puzzle_path.display(),
+            puzzle.day
+        );
+        process::exit(1);
+    };
+
+    let candidates = description.code_blocks();
+    if candidates.is_empty() {
+        eprintln!(
+            "No example candidates found in \"{}\".",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    }
+
+    println!("Example candidates in \"{}\":", puzzle_path.display());
+    print_candidates(&candidates);
+    println!();
+
+    let picks = if !picks.is_empty() {
+        picks.to_vec()
+    } else if io::stdin().is_terminal() {
+        prompt_picks(default_pick(&candidates))
+    } else {
+        vec![default_pick(&candidates)]
+    };
+
+    for (i, pick) in picks.iter().enumerate() {
+        let Some(code) = pick.checked_sub(1).and_then(|pick| candidates.get(pick)) else {
+            eprintln!("There is no example candidate [{pick}].");
+            process::exit(1);
+        };
+
+        let path = example_path(puzzle, i + 1);
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+
+        if !is_empty && !overwrite {
+            eprintln!(
+                "Example file \"{}\" is not empty, append `--overwrite` to replace it.",
+                path.display()
+            );
+            process::exit(1);
+        }
+
+        match fs::create_dir_all(path.parent().unwrap()).and_then(|()| fs::write(&path, code)) {
+            Ok(()) => println!("Wrote example [{pick}] to \"{}\"", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let module_path = puzzle.bin_path();
+    let Ok(mut source) = fs::read_to_string(&module_path) else {
+        println!("Module file \"{module_path}\" not found, skipping expected answers.");
+        return;
+    };
+
+    for part in [1, 2] {
+        let Some(answer) = description.example_answer(part) else {
+            continue;
+        };
+
+        match fill_expected(&source, part, &answer) {
+            Some(filled) => {
+                source = filled;
+                println!("Filled expected answer `{answer}` into the test of part {part}.");
+            }
+            None => println!("Kept the expected answer in the test of part {part}."),
+        }
+    }
+
+    if let Err(e) = fs::write(&module_path, source) {
+        eprintln!("Failed to write module file: {e}");
+        process::exit(1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{default_pick, example_path, fill_expected};
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+    use std::path::PathBuf;
+
+    const MODULE: &str = "advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<u32> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+}
+";
+
+    #[test]
+    fn fills_expected_answers() {
+        let filled = fill_expected(MODULE, 2, "CMZ").unwrap();
+        assert!(filled.contains("assert_eq!(result, Some(\"CMZ\".to_string()));"));
+        assert_eq!(filled.matches("assert_eq!(result, None);").count(), 1);
+
+        let filled = fill_expected(&filled, 1, "11").unwrap();
+        assert!(filled.contains("assert_eq!(result, Some(11));"));
+        assert!(!filled.contains("assert_eq!(result, None);"));
+    }
+
+    #[test]
+    fn keeps_expected_answers() {
+        let filled = fill_expected(MODULE, 1, "11").unwrap();
+        assert_eq!(fill_expected(&filled, 1, "12"), None);
+        assert_eq!(fill_expected(MODULE, 1, "abc"), None);
+    }
+
+    #[test]
+    fn picks_multiline_examples() {
+        assert_eq!(default_pick(&["x".into(), "1\n2\n".into()]), 2);
+        assert_eq!(default_pick(&["x".into()]), 1);
+    }
+
+    #[test]
+    fn names_example_files() {
+        let puzzle = PuzzleId::new(year!(2024), day!(1));
+        assert_eq!(
+            example_path(puzzle, 1),
+            PathBuf::from("data/2024/examples/01.txt")
+        );
+        assert_eq!(
+            example_path(puzzle, 2),
+            PathBuf::from("data/2024/examples/01-ex2.txt")
+        );
+    }
+}
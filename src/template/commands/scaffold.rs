@@ -1,15 +1,49 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::template::Day;
+use crate::template::{puzzle::Description, PuzzleId};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Directory with user templates. A user template takes precedence over a built-in template of the same name.
+const USER_TEMPLATES_DIR: &str = "./templates";
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+const DEFAULT_TEMPLATE: &str = "default";
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "parse-lines",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/parse-lines.txt"
+        )),
+    ),
+    (
+        "two-part-shared-parse",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/two-part-shared-parse.txt"
+        )),
+    ),
+];
+
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +53,11 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +65,118 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(USER_TEMPLATES_DIR) {
+        names.extend(entries.filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".txt").map(ToString::to_string)
+        }));
+    }
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+fn read_template(name: &str) -> Option<String> {
+    let user_path = Path::new(USER_TEMPLATES_DIR).join(format!("{name}.txt"));
+
+    fs::read_to_string(user_path).ok().or_else(|| {
+        BUILTIN_TEMPLATES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, template)| (*template).to_string())
+    })
+}
+
+/// Picks the return type of the parts that fits all known example answers.
+fn infer_return_type(answers: &[&str]) -> &'static str {
+    if answers.iter().all(|answer| answer.parse::<u64>().is_ok()) {
+        "u64"
+    } else if answers.iter().all(|answer| answer.parse::<i64>().is_ok()) {
+        "i64"
+    } else {
+        "String"
+    }
+}
+
+/// Formats an example answer as the expected result of a part.
+pub(crate) fn format_example(answer: Option<&str>, return_type: &str) -> String {
+    match answer {
+        Some(answer) if return_type == "String" => format!("Some({answer:?}.to_string())"),
+        Some(answer) if answer.parse::<i128>().is_ok() => format!("Some({answer})"),
+        _ => "None".into(),
+    }
+}
+
+/// Substitutes the placeholders of a template:
+///  - `%DAY_NUMBER%`, `%DAY%` and `%YEAR%`, e.g. `1`, `01` and `2024`.
+///  - `%YEAR_ARG%`, the year parameter of the `solution!` macro for puzzles outside of the default year.
+///  - `%TITLE%`, the title of the puzzle, if it was downloaded.
+///  - `%RETURN_TYPE%`, the return type of the parts.
+///  - `%PART_ONE_EXAMPLE%` and `%PART_TWO_EXAMPLE%`, the expected results of the examples, if known.
+fn render(
+    template: &str,
+    puzzle: PuzzleId,
+    description: Option<&Description>,
+    return_type: Option<&str>,
+) -> String {
+    // NOTE: solutions of the default year are declared by day only.
+    let year_arg = if puzzle.year.is_default() {
+        String::new()
+    } else {
+        format!(", year = {}", puzzle.year)
+    };
+
+    let title = description
+        .and_then(|d| d.title.clone())
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+
+    let examples = [1, 2].map(|part| description.and_then(|d| d.example_answer(part)));
+
+    let known_examples: Vec<&str> = examples.iter().flatten().map(String::as_str).collect();
+    let return_type = return_type.unwrap_or_else(|| infer_return_type(&known_examples));
+
+    template
+        .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%YEAR_ARG%", &year_arg)
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%TITLE%", &title)
+        .replace("%RETURN_TYPE%", return_type)
+        .replace(
+            "%PART_ONE_EXAMPLE%",
+            &format_example(examples[0].as_deref(), return_type),
+        )
+        .replace(
+            "%PART_TWO_EXAMPLE%",
+            &format_example(examples[1].as_deref(), return_type),
+        )
+}
+
+pub fn handle(
+    puzzle: PuzzleId,
+    overwrite: bool,
+    template: Option<&str>,
+    return_type: Option<&str>,
+) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = PathBuf::from(puzzle.bin_path());
+
+    let template_name = template.unwrap_or(DEFAULT_TEMPLATE);
+    let Some(template) = read_template(template_name) else {
+        eprintln!(
+            "Unknown template \"{template_name}\". Available templates: {}.",
+            available_templates().join(", ")
+        );
+        process::exit(1);
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +186,11 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let description = Description::read(puzzle);
+
+    match file.write_all(render(&template, puzzle, description.as_ref(), return_type).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -54,26 +198,93 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    // NOTE: inputs might already be downloaded, e.g. with `cargo scaffold <day> --download`.
+    if input_path.exists() {
+        println!("Using existing input file \"{}\"", input_path.display());
+    } else {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", input_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    if example_path.exists() {
+        println!("Using existing example file \"{}\"", example_path.display());
+    } else {
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", example_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if puzzle.year.is_default() {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_template, render};
+    use crate::{
+        day,
+        template::{puzzle::Description, PuzzleId, Year},
+    };
+
+    #[test]
+    fn renders_placeholders() {
+        let puzzle = PuzzleId::new(Year::default_year(), day!(1));
+        let description =
+            Description::parse("## --- Day 1: Historian Hysteria ---\n\nThe total is `*11*`.\n");
+        let module = render(
+            &read_template("default").unwrap(),
+            puzzle,
+            Some(&description),
+            None,
+        );
+
+        assert!(module.starts_with("// Day 1: Historian Hysteria\nadvent_of_code::solution!(1);"));
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<u64>"));
+        assert!(module.contains("assert_eq!(result, Some(11));"));
+        assert!(module.contains("assert_eq!(result, None);"));
+    }
+
+    #[test]
+    fn renders_string_examples() {
+        let puzzle = PuzzleId::new(Year::default_year(), day!(5));
+        let description = Description::parse("## --- Day 5 ---\n\nThe code is `*CMZ*`.\n");
+        let module = render(
+            "%RETURN_TYPE% %PART_ONE_EXAMPLE%",
+            puzzle,
+            Some(&description),
+            None,
+        );
+
+        assert_eq!(module, "String Some(\"CMZ\".to_string())");
+    }
+
+    #[test]
+    fn renders_without_description() {
+        let puzzle = PuzzleId::new(Year::default_year(), day!(5));
+        let module = render("// %TITLE% (%DAY%)", puzzle, None, Some("u32"));
+
+        assert_eq!(module, "// Day 5 (05)");
+    }
 }
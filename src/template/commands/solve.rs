@@ -1,9 +1,20 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::visualize::Visualization;
+use crate::template::{params::Params, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    alloc_stats: bool,
+    submit_part: Option<u8>,
+    params: &Params,
+    normalize: bool,
+    visualize: Option<Visualization>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -16,6 +27,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if alloc_stats && !dhat {
+        cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +38,16 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend(params.to_args());
+
+    if normalize {
+        cmd_args.push("--normalize".to_string());
+    }
+
+    if let Some(visualize) = visualize {
+        cmd_args.extend(visualize.to_args());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
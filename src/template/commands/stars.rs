@@ -0,0 +1,9 @@
+use crate::template::{readme_stars, Year};
+
+/// Rebuild the ⭐️ progress table in the readme from the submission log and the answers store.
+pub fn handle(year: Year) {
+    match readme_stars::update(year) {
+        Ok(()) => println!("Updated ⭐️ progress of {year} in the readme."),
+        Err(e) => eprintln!("Failed to update ⭐️ progress in the readme: {e:?}"),
+    }
+}
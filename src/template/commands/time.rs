@@ -1,40 +1,186 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
+use crate::template::benchmark_export;
+pub use crate::template::benchmark_export::ExportFormat;
+use crate::template::history::{self, History, Run};
+use crate::template::readme_benchmarks::TableOptions;
+use crate::template::registry::Solution;
+use crate::template::run_multi::{run_multi, Executor};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, alloc, readme_benchmarks, submissions, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Compare the benchmarks against stored runs, see [`history`].
+pub struct Compare {
+    /// The name or commit of the run to compare against. Defaults to the latest stored timing of every day.
+    pub baseline: Option<String>,
+    /// Days that became slower by more than this many percent are regressions.
+    pub threshold: f64,
+}
+
+/// Bench a set of days of a year. Passing a registry runs the days in-process instead of spawning their solution binaries.
+/// Days that run longer than `timeout` are stopped and not stored.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    name: Option<String>,
+    compare: Option<Compare>,
+    exports: &[ExportFormat],
+    alloc_stats: bool,
+    timeout: Option<Duration>,
+    registry: Option<&[Solution]>,
+) {
+    if alloc_stats && registry.is_some() && !alloc::is_enabled() {
+        eprintln!(
+            "Allocation statistics of in-process runs require the `alloc-stats` feature, e.g. \
+            `cargo run --release --features alloc-stats -- time --in-process`."
+        );
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
+    let mut history = match History::read_from_file() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e}");
+            process::exit(1);
+        }
+    };
+
+    // NOTE: resolve the baseline before benching, so that a misspelled baseline does not waste a run.
+    // Without any stored run, every day is reported as new.
+    let baseline = compare.as_ref().map(|compare| {
+        match (
+            history.baseline(compare.baseline.as_deref()),
+            &compare.baseline,
+        ) {
+            (Some(baseline), _) => baseline,
+            (None, None) => Timings::default(),
+            (None, Some(name)) => {
+                eprintln!("No stored run with the name or commit `{name}` to compare against.");
+                process::exit(1);
+            }
+        }
+    });
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if run_all || compare.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| !stored_timings.is_day_complete(PuzzleId::new(year, *day)))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: days always run one after another, so that concurrent runs do not skew the benchmarks.
+    let executor = Executor::new(true, 1, registry)
+        .with_alloc_stats(alloc_stats)
+        .with_timeout(timeout);
+    let timings = run_multi(year, &days_to_run, true, executor)
+        .timings
+        .unwrap();
+
+    let has_regressions = compare.zip(baseline).is_some_and(|(compare, baseline)| {
+        print_comparison(&baseline, &timings, compare.threshold)
+    });
 
     if store {
+        history.push(Run {
+            timestamp: submissions::now(),
+            commit: history::current_commit(),
+            name,
+            timings: timings.clone(),
+        });
+
+        if let Err(e) = history.store_file() {
+            eprintln!("Failed to store benchmark history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+        let options = TableOptions::from_env();
+        // NOTE: the readme only lists the benchmarks of the default year.
+        if year.is_default() && options.enabled {
+            match readme_benchmarks::update(merged_timings.for_year(year), &options) {
+                Ok(()) => {
+                    println!("Stored updated benchmarks.");
+                }
+                Err(_) => {
+                    eprintln!("Failed to store updated benchmarks.");
+                }
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        } else {
+            println!("Stored updated benchmarks.");
+        }
+    }
+
+    if !exports.is_empty() {
+        // NOTE: exports include the stored timings of days that were not benched in this run.
+        let export_timings = stored_timings.merge(&timings).for_year(year);
+        println!();
+
+        for format in exports {
+            match benchmark_export::export(&export_timings, year, *format) {
+                Ok(path) => println!("Exported benchmarks to \"{}\".", path.display()),
+                Err(e) => eprintln!("Failed to export benchmarks: {e}"),
             }
         }
     }
+
+    if has_regressions {
+        process::exit(1);
+    }
+}
+
+/// Prints the change of every benched day and returns whether any day regressed.
+fn print_comparison(baseline: &Timings, timings: &Timings, threshold: f64) -> bool {
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("----------");
+
+    let mut has_regressions = false;
+
+    for delta in history::compare(baseline, timings) {
+        let current = format_millis(delta.current_nanos);
+
+        let line = match (delta.baseline_nanos, delta.change()) {
+            (Some(baseline), Some(change)) => {
+                let verdict = if delta.is_regression(threshold) {
+                    has_regressions = true;
+                    " ✖ regression"
+                } else {
+                    ""
+                };
+                format!(
+                    "{} → {current} ({change:+.1}%){verdict}",
+                    format_millis(baseline)
+                )
+            }
+            _ => format!("{current} (new)"),
+        };
+
+        println!("Day {}: {line}", delta.puzzle.day);
+    }
+
+    if has_regressions {
+        println!("\nSome days became slower by more than {threshold}%.");
+    }
+
+    has_regressions
+}
+
+fn format_millis(nanos: f64) -> String {
+    format!("{:.3}ms", nanos / 1_000_000_f64)
 }
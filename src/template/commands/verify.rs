@@ -0,0 +1,207 @@
+use std::process;
+
+use crate::template::answers::{Answer, Answers, Verdict};
+use crate::template::registry::Solution;
+use crate::template::report::{PartReport, PARSE_PART};
+use crate::template::run_multi::{run_multi, DayStatus, Executor};
+use crate::template::{all_days, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Verify the answers of all days of a year. Passing a registry runs the days in-process instead of spawning their solution binaries.
+pub fn handle(year: Year, is_release: bool, store: bool, registry: Option<&[Solution]>) {
+    let stored_answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read stored answers: {e}");
+            process::exit(1);
+        }
+    };
+    let executor = Executor::new(is_release, 1, registry);
+    let run = run_multi(year, &all_days().collect(), false, executor);
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------------");
+
+    let mut current_answers = Answers::default();
+    let mut failed = false;
+
+    for &(puzzle, status) in &run.statuses {
+        let reports = run
+            .reports
+            .iter()
+            .find(|(reported, _)| *reported == puzzle)
+            .map_or(&[][..], |(_, reports)| reports.as_slice());
+
+        let Some((line, day_failed)) = verify_day(&stored_answers, puzzle, reports, status) else {
+            continue;
+        };
+
+        println!("{line}");
+        failed |= day_failed;
+
+        if !reports.is_empty() {
+            current_answers.data.push(Answer {
+                year: puzzle.year,
+                day: puzzle.day,
+                part_1: part_answer(reports, 1),
+                part_2: part_answer(reports, 2),
+            });
+        }
+    }
+
+    if store {
+        match stored_answers.merge(&current_answers).store_file() {
+            Ok(()) => println!("\nStored current answers as the new baseline."),
+            Err(e) => {
+                eprintln!("\nFailed to store answers: {e}");
+                process::exit(1);
+            }
+        }
+    } else if failed {
+        process::exit(1);
+    }
+}
+
+fn part_answer(reports: &[PartReport], part: u8) -> Option<String> {
+    reports
+        .iter()
+        .find(|report| report.part == part)
+        .and_then(|report| report.answer.clone())
+}
+
+/// Compares the reported parts of a day with the stored answers and returns the line to print, and whether it failed.
+/// A stored part that was not reported fails, as does a day with stored answers that did not finish (e.g. it panicked).
+/// Returns `None` for days without reports and stored answers.
+fn verify_day(
+    stored_answers: &Answers,
+    puzzle: PuzzleId,
+    reports: &[PartReport],
+    status: DayStatus,
+) -> Option<(String, bool)> {
+    let has_stored_answer = [1, 2]
+        .into_iter()
+        .any(|part| stored_answers.get(puzzle, part).is_some());
+    let has_reports = reports.iter().any(|report| report.part != PARSE_PART);
+
+    if !has_reports && !has_stored_answer {
+        return None;
+    }
+
+    let mut failed = status != DayStatus::Ok && has_stored_answer;
+    let mut verdicts = vec![];
+
+    for part in [1, 2] {
+        let report = reports.iter().find(|report| report.part == part);
+
+        let verdict = match (report, stored_answers.get(puzzle, part)) {
+            (Some(report), _) => {
+                match stored_answers.verify(puzzle, part, report.answer.as_deref()) {
+                    Verdict::Pass => "✔".to_string(),
+                    Verdict::Missing => "? (no stored answer)".to_string(),
+                    Verdict::Fail { expected, actual } => {
+                        failed = true;
+                        let actual = actual.unwrap_or_else(|| "✖".into());
+                        format!("✖ (expected {expected}, got {actual})")
+                    }
+                }
+            }
+            (None, Some(expected)) => {
+                failed = true;
+                format!("✖ (expected {expected}, not reported)")
+            }
+            (None, None) => continue,
+        };
+
+        verdicts.push(format!("Part {part}: {verdict}"));
+    }
+
+    let header = if status == DayStatus::Ok {
+        format!("Day {}", puzzle.day)
+    } else {
+        format!("Day {} ({status})", puzzle.day)
+    };
+
+    Some((format!("{header}: {}", verdicts.join(", ")), failed))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::verify_day;
+    use crate::template::{
+        answers::{Answer, Answers},
+        report::PartReport,
+        run_multi::DayStatus,
+        PuzzleId,
+    };
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn stored_answers() -> Answers {
+        Answers {
+            data: vec![Answer {
+                year: year!(2024),
+                day: day!(5),
+                part_1: Some("11".into()),
+                part_2: Some("31".into()),
+            }],
+        }
+    }
+
+    fn report(part: u8, answer: &str) -> PartReport {
+        PartReport {
+            part,
+            answer: Some(answer.into()),
+            duration: Duration::ZERO,
+            stats: None,
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn passes_matching_days() {
+        let puzzle = PuzzleId::new(year!(2024), day!(5));
+        let reports = [report(1, "11"), report(2, "31")];
+        assert_eq!(
+            verify_day(&stored_answers(), puzzle, &reports, DayStatus::Ok),
+            Some(("Day 05: Part 1: ✔, Part 2: ✔".into(), false))
+        );
+    }
+
+    #[test]
+    fn fails_days_that_did_not_finish() {
+        let puzzle = PuzzleId::new(year!(2024), day!(5));
+
+        assert_eq!(
+            verify_day(
+                &stored_answers(),
+                puzzle,
+                &[report(1, "11")],
+                DayStatus::Panicked
+            ),
+            Some((
+                "Day 05 (panicked): Part 1: ✔, Part 2: ✖ (expected 31, not reported)".into(),
+                true
+            ))
+        );
+
+        let (line, failed) =
+            verify_day(&stored_answers(), puzzle, &[], DayStatus::TimedOut).unwrap();
+        assert!(failed);
+        assert!(line.starts_with("Day 05 (timed out): Part 1: ✖"));
+    }
+
+    #[test]
+    fn skips_days_without_answers() {
+        let puzzle = PuzzleId::new(year!(2024), day!(6));
+        assert_eq!(
+            verify_day(&stored_answers(), puzzle, &[], DayStatus::NotSolved),
+            None
+        );
+        assert_eq!(
+            verify_day(&stored_answers(), puzzle, &[report(1, "2")], DayStatus::Ok),
+            Some(("Day 06: Part 1: ? (no stored answer)".into(), false))
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::report::PARSE_PART;
+use crate::template::{run_multi::child_commands, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The change of a part's answer compared to the previous run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum AnswerChange {
+    New(Option<String>),
+    Unchanged(Option<String>),
+    Changed {
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+/// Watch the source, example and input files of a puzzle and rerun its example tests and solution on every change.
+/// Files are polled, so this works without any external watcher tools.
+pub fn handle(puzzle: PuzzleId, release: bool) {
+    println!(
+        "Watching the files of day {} for changes. Press Ctrl+C to stop.",
+        puzzle.day
+    );
+
+    let mut last_modified = BTreeMap::new();
+    let mut answers: Option<BTreeMap<u8, Option<String>>> = None;
+
+    loop {
+        let modified = modification_times(puzzle);
+
+        if modified != last_modified {
+            last_modified = modified;
+            answers = Some(run(puzzle, release, answers.as_ref()));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The files of a puzzle that are watched: its solution, its examples and manifest, and its input.
+fn watched_files(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(puzzle.bin_path()),
+        puzzle.data_path("inputs", "txt"),
+    ];
+
+    // NOTE: includes additional examples (e.g. `14-2.txt`) and the manifest (`14.json`).
+    let prefix = puzzle.day.to_string();
+    if let Ok(entries) = fs::read_dir(puzzle.data_dir("examples")) {
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| {
+                            stem == prefix || stem.starts_with(&format!("{prefix}-"))
+                        })
+                }),
+        );
+    }
+
+    files
+}
+
+/// The modification time of every watched file. Missing files are included, so that creating them counts as a change.
+fn modification_times(puzzle: PuzzleId) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    watched_files(puzzle)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Runs the example tests and the solution, then prints the answers compared to the previous run.
+fn run(
+    puzzle: PuzzleId,
+    release: bool,
+    previous: Option<&BTreeMap<u8, Option<String>>>,
+) -> BTreeMap<u8, Option<String>> {
+    println!("\n{ANSI_BOLD}Examples{ANSI_RESET}");
+    println!("--------");
+
+    let mut args = vec!["test", "--quiet", "--bin"];
+    let bin_name = puzzle.bin_name();
+    args.push(&bin_name);
+    if release {
+        args.push("--release");
+    }
+
+    let examples_passed = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success());
+
+    if examples_passed {
+        println!("✔ examples passed.");
+    } else {
+        println!("✖ examples failed.");
+    }
+
+    println!("\n{ANSI_BOLD}Solution{ANSI_RESET}");
+    println!("--------");
+
+    let answers: BTreeMap<u8, Option<String>> =
+        match child_commands::run_solution(puzzle, false, release, false, false, None) {
+            Ok(output) => output
+                .reports
+                .into_iter()
+                .filter(|report| report.part != PARSE_PART)
+                .map(|report| (report.part, report.answer))
+                .collect(),
+            Err(e) => {
+                eprintln!("Failed to run solution: {e:?}");
+                return previous.cloned().unwrap_or_default();
+            }
+        };
+
+    if let Some(previous) = previous {
+        println!("\n{ANSI_BOLD}Changes{ANSI_RESET}");
+        println!("-------");
+
+        for (part, change) in diff_answers(previous, &answers) {
+            println!("Part {part}: {}", format_change(&change));
+        }
+    }
+
+    println!("\n{ANSI_ITALIC}Waiting for changes...{ANSI_RESET}");
+    answers
+}
+
+fn diff_answers(
+    previous: &BTreeMap<u8, Option<String>>,
+    current: &BTreeMap<u8, Option<String>>,
+) -> Vec<(u8, AnswerChange)> {
+    current
+        .iter()
+        .filter(|(part, _)| **part != PARSE_PART)
+        .map(|(part, answer)| {
+            let change = match previous.get(part) {
+                None => AnswerChange::New(answer.clone()),
+                Some(previous) if previous == answer => AnswerChange::Unchanged(answer.clone()),
+                Some(previous) => AnswerChange::Changed {
+                    from: previous.clone(),
+                    to: answer.clone(),
+                },
+            };
+            (*part, change)
+        })
+        .collect()
+}
+
+fn format_change(change: &AnswerChange) -> String {
+    let format = |answer: &Option<String>| answer.clone().unwrap_or_else(|| "✖".into());
+
+    match change {
+        AnswerChange::New(answer) => format!("{} (new)", format(answer)),
+        AnswerChange::Unchanged(answer) => format!("{} (unchanged)", format(answer)),
+        AnswerChange::Changed { from, to } => format!("{} → {}", format(from), format(to)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{diff_answers, format_change, AnswerChange};
+    use std::collections::BTreeMap;
+
+    fn answers(values: &[(u8, Option<&str>)]) -> BTreeMap<u8, Option<String>> {
+        values
+            .iter()
+            .map(|(part, answer)| (*part, answer.map(String::from)))
+            .collect()
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let previous = answers(&[(1, Some("11")), (2, None)]);
+        let current = answers(&[(0, None), (1, Some("11")), (2, Some("31"))]);
+
+        assert_eq!(
+            diff_answers(&previous, &current),
+            vec![
+                (1, AnswerChange::Unchanged(Some("11".into()))),
+                (
+                    2,
+                    AnswerChange::Changed {
+                        from: None,
+                        to: Some("31".into())
+                    }
+                ),
+            ]
+        );
+
+        assert_eq!(
+            diff_answers(&BTreeMap::new(), &current)[0],
+            (1, AnswerChange::New(Some("11".into())))
+        );
+    }
+
+    #[test]
+    fn formats_changes() {
+        assert_eq!(
+            format_change(&AnswerChange::Changed {
+                from: Some("11".into()),
+                to: None
+            }),
+            "11 → ✖"
+        );
+        assert_eq!(
+            format_change(&AnswerChange::Unchanged(Some("31".into()))),
+            "31 (unchanged)"
+        );
+    }
+}
@@ -0,0 +1,345 @@
+/// History of benchmark runs, used to compare the current benchmarks against previous runs.
+/// Every `cargo time --store` appends a run, while `timings.json` only keeps the latest timing of each day.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    process::Command,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::Timings;
+use crate::template::PuzzleId;
+
+static HISTORY_FILE_PATH: &str = "./data/timings-history.json";
+
+/// A single, stored benchmark run.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The git commit the run was benched on, if available.
+    pub commit: Option<String>,
+    /// An optional name of the run, to use it as a baseline.
+    pub name: Option<String>,
+    pub timings: Timings,
+}
+
+impl Run {
+    /// Whether the run is referenced by a baseline, i.e. by its name or a prefix of its commit.
+    fn matches(&self, baseline: &str) -> bool {
+        self.name.as_deref() == Some(baseline)
+            || self
+                .commit
+                .as_deref()
+                .is_some_and(|commit| commit.starts_with(baseline))
+    }
+}
+
+/// All stored benchmark runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Dehydrate the history to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(HISTORY_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from a JSON file. If not present, returns an empty history.
+    /// Fails if the file can not be read or parsed, as storing would otherwise drop the previous runs.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(HISTORY_FILE_PATH) {
+            Ok(s) => History::try_from(s)
+                .map_err(|e| format!("could not parse \"{HISTORY_FILE_PATH}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(format!("could not read \"{HISTORY_FILE_PATH}\": {e}")),
+        }
+    }
+
+    pub fn push(&mut self, run: Run) {
+        self.runs.push(run);
+    }
+
+    /// The timings to compare against.
+    /// Without a baseline, this is the latest stored timing of every day.
+    /// With a baseline, these are the timings of the latest run with that name or commit.
+    pub fn baseline(&self, baseline: Option<&str>) -> Option<Timings> {
+        match baseline {
+            Some(baseline) => self
+                .runs
+                .iter()
+                .rev()
+                .find(|run| run.matches(baseline))
+                .map(|run| run.timings.clone()),
+            None if self.runs.is_empty() => None,
+            None => Some(
+                self.runs
+                    .iter()
+                    .fold(Timings::default(), |acc, run| acc.merge(&run.timings)),
+            ),
+        }
+    }
+}
+
+/// The short hash of the current git commit, if the repository is a git repository.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The change of the benchmarked time of a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub puzzle: PuzzleId,
+    /// Not present for days that were not benched in the baseline.
+    pub baseline_nanos: Option<f64>,
+    pub current_nanos: f64,
+}
+
+impl Delta {
+    /// The relative change in percent, e.g. `25.0` if the day became 25% slower.
+    pub fn change(&self) -> Option<f64> {
+        self.baseline_nanos
+            .filter(|baseline| *baseline > 0.0)
+            .map(|baseline| (self.current_nanos - baseline) / baseline * 100.0)
+    }
+
+    /// Whether the day became slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Compares the total time of every day of the current timings against the baseline.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<Delta> {
+    current
+        .data
+        .iter()
+        .map(|timing| Delta {
+            puzzle: timing.puzzle(),
+            baseline_nanos: baseline
+                .data
+                .iter()
+                .find(|t| t.puzzle() == timing.puzzle())
+                .map(|t| t.total_nanos),
+            current_nanos: timing.total_nanos,
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&History> for JsonValue {
+    fn from(value: &History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.runs.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(History {
+            runs: json_runs
+                .iter()
+                .map(Run::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional = |value: &Option<String>| match value {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), optional(&value.commit));
+        map.insert("name".into(), optional(&value.name));
+        map.insert("timings".into(), JsonValue::from(value.timings.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected run.commit to be null or string.")?;
+
+        let name = json
+            .get("name")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected run.name to be null or string.")?;
+
+        let timings = Timings::try_from(
+            json.get("timings")
+                .ok_or("Expected run to have key `timings`.")?,
+        )?;
+
+        Ok(Run {
+            timestamp,
+            commit: commit.cloned(),
+            name: name.cloned(),
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, History, Run};
+    use crate::{
+        day,
+        template::{
+            timings::{PartTiming, Timing, Timings},
+            PuzzleId,
+        },
+        year,
+    };
+    use tinyjson::JsonValue;
+
+    fn timing(day: u8, total_nanos: f64) -> Timing {
+        let puzzle = PuzzleId::new(year!(2024), crate::template::Day::new(day).unwrap());
+        Timing {
+            part_1: Some(PartTiming::new("1ms")),
+            total_nanos,
+            ..Timing::new(puzzle)
+        }
+    }
+
+    fn run(name: Option<&str>, commit: &str, data: Vec<Timing>) -> Run {
+        Run {
+            timestamp: 1_733_000_000,
+            commit: Some(commit.into()),
+            name: name.map(String::from),
+            timings: Timings { data },
+        }
+    }
+
+    fn history() -> History {
+        History {
+            runs: vec![
+                run(
+                    Some("before"),
+                    "abc1234",
+                    vec![timing(1, 100.0), timing(2, 200.0)],
+                ),
+                run(None, "def5678", vec![timing(2, 300.0)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn selects_latest_timings_as_baseline() {
+        let baseline = history().baseline(None).unwrap();
+        assert_eq!(baseline.data.len(), 2);
+        assert_eq!(baseline.data[0].total_nanos, 100.0);
+        assert_eq!(baseline.data[1].total_nanos, 300.0);
+        assert!(History::default().baseline(None).is_none());
+    }
+
+    #[test]
+    fn selects_named_baselines() {
+        let history = history();
+        assert_eq!(
+            history.baseline(Some("before")).unwrap().data[1].total_nanos,
+            200.0
+        );
+        assert_eq!(history.baseline(Some("def")).unwrap().data.len(), 1);
+        assert!(history.baseline(Some("after")).is_none());
+    }
+
+    #[test]
+    fn computes_deltas() {
+        let baseline = history().baseline(None).unwrap();
+        let current = Timings {
+            data: vec![timing(1, 125.0), timing(2, 270.0), timing(3, 50.0)],
+        };
+        let deltas = compare(&baseline, &current);
+
+        assert_eq!(deltas[0].puzzle, PuzzleId::new(year!(2024), day!(1)));
+        assert_eq!(deltas[0].change(), Some(25.0));
+        assert!(deltas[0].is_regression(10.0));
+        assert!(!deltas[0].is_regression(30.0));
+
+        assert_eq!(deltas[1].change(), Some(-10.0));
+        assert!(!deltas[1].is_regression(0.0));
+
+        assert_eq!(deltas[2].change(), None);
+        assert!(!deltas[2].is_regression(0.0));
+    }
+
+    #[test]
+    fn roundtrips_history() {
+        let json = JsonValue::from(&history()).stringify().unwrap();
+        let parsed = History::try_from(json).unwrap();
+        assert_eq!(parsed.runs.len(), 2);
+        assert_eq!(parsed.runs[0].name.as_deref(), Some("before"));
+        assert_eq!(parsed.runs[1].name, None);
+        assert_eq!(parsed.runs[1].commit.as_deref(), Some("def5678"));
+        assert_eq!(parsed.runs[1].timestamp, 1_733_000_000);
+        assert_eq!(parsed.runs[0].timings.data.len(), 2);
+    }
+}
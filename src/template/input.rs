@@ -0,0 +1,223 @@
+//! Sanity checks of puzzle inputs, run before a solution reads its input.
+//!
+//! Missing and empty inputs (e.g. left behind by `scaffold` or an interrupted download) stop the run with a message
+//! that names the file, instead of a panic deep in parsing. CRLF line endings and trailing whitespace are reported
+//! as warnings. Passing `--normalize` rewrites the input file with LF line endings.
+
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::PathBuf,
+    process,
+};
+
+use crate::template::{commands::download, PuzzleId};
+
+/// The exit code of a solution binary whose input can not be used.
+pub const INVALID_INPUT_EXIT_CODE: i32 = 3;
+
+/// A problem with the input file of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Issue {
+    Missing,
+    Empty,
+    CrlfLineEndings,
+    /// A line ends with spaces or tabs, or the input ends with blank lines.
+    TrailingWhitespace,
+}
+
+impl Issue {
+    /// Whether the input can not be used at all.
+    pub fn is_fatal(self) -> bool {
+        matches!(self, Issue::Missing | Issue::Empty)
+    }
+}
+
+/// An issue of the input file of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    pub puzzle: PuzzleId,
+    pub path: PathBuf,
+    pub issue: Issue,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path.display();
+
+        let download = if self.puzzle.year.is_default() {
+            format!("cargo download {}", self.puzzle.day)
+        } else {
+            format!(
+                "cargo download {} --year {}",
+                self.puzzle.day, self.puzzle.year
+            )
+        };
+
+        match self.issue {
+            Issue::Missing => write!(
+                f,
+                "input file \"{path}\" does not exist. Run `{download}` to download it."
+            ),
+            Issue::Empty => write!(
+                f,
+                "input file \"{path}\" is empty. Run `{download}` to download it."
+            ),
+            Issue::CrlfLineEndings => write!(
+                f,
+                "input file \"{path}\" has CRLF line endings. Append `--normalize` to convert them."
+            ),
+            Issue::TrailingWhitespace => {
+                write!(f, "input file \"{path}\" has trailing whitespace.")
+            }
+        }
+    }
+}
+
+/// Checks the contents of an input.
+pub fn check(input: &str) -> Vec<Issue> {
+    let mut issues = vec![];
+
+    if input.trim().is_empty() {
+        issues.push(Issue::Empty);
+        return issues;
+    }
+
+    if input.contains("\r\n") {
+        issues.push(Issue::CrlfLineEndings);
+    }
+
+    let has_trailing_whitespace = input.lines().any(|line| line.ends_with([' ', '\t']))
+        || input.replace("\r\n", "\n").ends_with("\n\n");
+
+    if has_trailing_whitespace {
+        issues.push(Issue::TrailingWhitespace);
+    }
+
+    issues
+}
+
+/// Converts CRLF line endings to LF.
+pub fn normalize_line_endings(input: &str) -> String {
+    input.replace("\r\n", "\n")
+}
+
+/// Reads and checks the input of a puzzle. Warnings are printed to stderr.
+/// With `normalize`, an input with CRLF line endings is rewritten with LF line endings.
+///
+/// # Errors
+/// If the input is missing or empty.
+pub fn read(puzzle: PuzzleId, normalize: bool) -> Result<String, InputError> {
+    let path = puzzle.data_path("inputs", "txt");
+    let error = |issue| InputError {
+        puzzle,
+        path: path.clone(),
+        issue,
+    };
+
+    let mut input = fs::read_to_string(&path).map_err(|_| error(Issue::Missing))?;
+
+    for issue in check(&input) {
+        if issue.is_fatal() {
+            return Err(error(issue));
+        }
+
+        if issue == Issue::CrlfLineEndings && normalize {
+            input = normalize_line_endings(&input);
+            match fs::write(&path, &input) {
+                Ok(()) => eprintln!("Normalized line endings of \"{}\".", path.display()),
+                Err(e) => eprintln!("Failed to normalize \"{}\": {e}", path.display()),
+            }
+            continue;
+        }
+
+        eprintln!("Warning: {}", error(issue));
+    }
+
+    Ok(input)
+}
+
+/// Reads the input of a puzzle for a solution binary, see [`read`].
+/// Reads `--normalize` from the command-line arguments. If the input is missing or empty, offers to download it
+/// when running in a terminal, and exits with [`INVALID_INPUT_EXIT_CODE`] otherwise.
+pub fn read_or_exit(puzzle: PuzzleId) -> String {
+    let normalize = env::args().any(|arg| arg == "--normalize");
+
+    let error = match read(puzzle, normalize) {
+        Ok(input) => return input,
+        Err(error) => error,
+    };
+
+    eprintln!("Error: {error}");
+
+    if is_interactive() && confirm(&format!("Download the input of day {} now?", puzzle.day)) {
+        download::handle(puzzle);
+        if let Ok(input) = read(puzzle, normalize) {
+            return input;
+        }
+    }
+
+    process::exit(INVALID_INPUT_EXIT_CODE);
+}
+
+/// Whether the user can answer a prompt, i.e. the binary is not run by `cargo all` or in CI.
+fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).ok();
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, normalize_line_endings, InputError, Issue};
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn checks_inputs() {
+        assert_eq!(check("1 2\n3 4\n"), vec![]);
+        assert_eq!(check(""), vec![Issue::Empty]);
+        assert_eq!(check("\n  \n"), vec![Issue::Empty]);
+        assert_eq!(check("1 2\r\n3 4\r\n"), vec![Issue::CrlfLineEndings]);
+        assert_eq!(check("1 2 \n3 4\n"), vec![Issue::TrailingWhitespace]);
+        assert_eq!(check("1 2\n3 4\n\n"), vec![Issue::TrailingWhitespace]);
+        assert_eq!(
+            check("1 2\r\n3 4\r\n\r\n"),
+            vec![Issue::CrlfLineEndings, Issue::TrailingWhitespace]
+        );
+        // blank lines between blocks are part of many inputs.
+        assert_eq!(check("1\n2\n\n3\n"), vec![]);
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize_line_endings("1 2\r\n3 4\r\n"), "1 2\n3 4\n");
+        assert_eq!(check(&normalize_line_endings("1 2\r\n3 4\r\n")), vec![]);
+    }
+
+    #[test]
+    fn formats_errors() {
+        let puzzle = PuzzleId::new(year!(2023), day!(19));
+        let error = InputError {
+            puzzle,
+            path: puzzle.data_path("inputs", "txt"),
+            issue: Issue::Empty,
+        };
+        assert_eq!(
+            error.to_string(),
+            "input file \"data/2023/inputs/19.txt\" is empty. Run `cargo download 19 --year 2023` to download it."
+        );
+        assert!(Issue::Missing.is_fatal());
+        assert!(!Issue::CrlfLineEndings.is_fatal());
+    }
+}
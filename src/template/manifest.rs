@@ -0,0 +1,231 @@
+//! Per-day example manifests, listing the examples of a puzzle with their expected answers.
+//!
+//! # Format
+//! The manifest of a day is stored next to its examples, e.g. `data/2024/examples/14.json`:
+//!
+//! ```json
+//! {
+//!     "examples": [
+//!         { "file": "14.txt", "part_1": "12", "part_2": null, "params": { "width": 11, "height": 7 } }
+//!     ]
+//! }
+//! ```
+//!
+//! An example applies to the parts that have an expected answer. `file` defaults to the day's example file
+//! (e.g. `14.txt`), `params` are optional.
+
+use std::{collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    params::{self, Params},
+    registry::Solution,
+    PuzzleId,
+};
+
+/// A single example of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// The file name of the example, relative to the examples directory of the year.
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Parameters of the puzzle that differ between the example and the real input, e.g. a grid size.
+    pub params: Params,
+}
+
+impl Example {
+    /// The expected answer of a part (1 or 2), if the example applies to it.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The examples of a single puzzle.
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    /// Reads the manifest of a puzzle. If not present, returns an empty manifest.
+    ///
+    /// # Panics
+    /// If the manifest is present, but malformed.
+    pub fn read(puzzle: PuzzleId) -> Self {
+        let path = puzzle.data_path("examples", "json");
+        match fs::read_to_string(&path) {
+            Ok(json) => Manifest::parse(&json, puzzle)
+                .unwrap_or_else(|e| panic!("malformed manifest \"{}\": {e}", path.display())),
+            Err(_) => Manifest::default(),
+        }
+    }
+
+    fn parse(json: &str, puzzle: PuzzleId) -> Result<Self, String> {
+        let json = JsonValue::from_str(json).or(Err("not valid JSON file."))?;
+
+        let json_examples = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.examples` to be an array.")?;
+
+        Ok(Manifest {
+            examples: json_examples
+                .iter()
+                .map(|example| Example::from_json(example, puzzle))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Formats a JSON value as a parameter value. Whole numbers are formatted without a fraction.
+#[allow(clippy::cast_possible_truncation)]
+fn param_to_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Number(n) if n.fract() == 0.0 => Some((*n as i64).to_string()),
+        JsonValue::Number(n) => Some(n.to_string()),
+        JsonValue::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+impl Example {
+    fn from_json(value: &JsonValue, puzzle: PuzzleId) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = match json.get("file") {
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .ok_or("Expected example.file to be a string.")?,
+            None => format!("{}.txt", puzzle.day),
+        };
+
+        let expected = |part: u8| {
+            json.get(&format!("part_{part}"))
+                .filter(|v| !v.is_null())
+                .map(|v| {
+                    param_to_string(v).ok_or(format!(
+                        "Expected example.part_{part} to be null or a string."
+                    ))
+                })
+                .transpose()
+        };
+
+        let params = match json.get("params") {
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected example.params to be an object.")?
+                .iter()
+                .map(|(key, value)| {
+                    param_to_string(value)
+                        .map(|value| (key.clone(), value))
+                        .ok_or(format!(
+                            "Expected example.params.{key} to be a string or number."
+                        ))
+                })
+                .collect::<Result<_, _>>()?,
+            None => Params::default(),
+        };
+
+        Ok(Example {
+            file,
+            part_1: expected(1)?,
+            part_2: expected(2)?,
+            params,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Runs every example listed in the manifest of a solution's puzzle against its parts.
+///
+/// # Panics
+/// If an example file is missing or any part does not return the expected answer.
+pub fn check_examples(solution: &Solution) {
+    let puzzle = (solution.puzzle)();
+    let mut failures: Vec<String> = vec![];
+
+    for example in Manifest::read(puzzle).examples {
+        let path = puzzle.data_dir("examples").join(&example.file);
+        let input = fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("could not open example file \"{}\"", path.display()));
+
+        for part in [1, 2] {
+            let (Some(expected), Some(run_part)) = (example.expected(part), solution.part(part))
+            else {
+                continue;
+            };
+
+            let actual = params::with(&example.params, || run_part(&input, false)).answer;
+            if actual.as_deref() != Some(expected) {
+                failures.push(format!(
+                    "{} part {part}: expected {expected}, got {}",
+                    example.file,
+                    actual.as_deref().unwrap_or("nothing")
+                ));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "examples did not match:\n{}",
+        failures.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Manifest;
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn parses_manifests() {
+        let json = r#"{ "examples": [
+            { "part_1": "12", "params": { "width": 11, "height": 7, "name": "small" } },
+            { "file": "14-2.txt", "part_1": null, "part_2": 31 }
+        ] }"#;
+
+        let manifest = Manifest::parse(json, PuzzleId::new(year!(2024), day!(14))).unwrap();
+        assert_eq!(manifest.examples.len(), 2);
+
+        let first = &manifest.examples[0];
+        assert_eq!(first.file, "14.txt");
+        assert_eq!(first.expected(1), Some("12"));
+        assert_eq!(first.expected(2), None);
+        assert_eq!(first.params.get::<u32>("width"), Some(11));
+        assert_eq!(
+            first.params.get::<String>("name"),
+            Some("small".to_string())
+        );
+
+        let second = &manifest.examples[1];
+        assert_eq!(second.file, "14-2.txt");
+        assert_eq!(second.expected(1), None);
+        assert_eq!(second.expected(2), Some("31"));
+        assert!(second.params.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_manifests() {
+        let puzzle = PuzzleId::new(year!(2024), day!(14));
+        assert!(Manifest::parse(r#"{ "examples": [{ "part_1": [] }] }"#, puzzle).is_err());
+        assert!(Manifest::parse(r#"{ "data": [] }"#, puzzle).is_err());
+    }
+}
@@ -1,68 +1,169 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod input;
+pub mod manifest;
+pub mod params;
+pub mod puzzle;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod submissions;
+pub mod visualize;
 
 pub use day::*;
+pub use year::*;
 
+mod benchmark_export;
 mod day;
+mod history;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Passing a [`Day`] reads the file of the default year, e.g. `data/2024/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<PuzzleId>, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path_with_suffix(
+        folder,
+        &format!("-{part}"),
+        "txt",
+    ));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Also creates the constant `SOLUTION`, which registers the parts for in-process runs,
+/// a test that runs the examples listed in the day's example manifest
+/// and the function `params()`, which returns the parameters of the current run.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Solutions of a year other than the default year (`AOC_YEAR`) declare it with a trailing `year = 2023` parameter.
+///
+/// Solutions that share their parsed input between both parts pass a `parse = <function>` parameter instead.
+/// Their parts take a reference to the parsed input, e.g. `fn part_one(input: &Input) -> Option<u32>`
+/// (or `&[T]` if it is a `Vec<T>`),
+/// and the parse step is timed separately.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, [$($year)?], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1 $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, [$($year)?], [part_one, 1]);
+    };
+    ($day:expr, 2 $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, [$($year)?], [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr $(, year = $year:expr)?) => {
+        $crate::solution!(@impl_parsed $day, [$($year)?], $parse, [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+
+    (@impl $day:expr, [$($year:expr)?], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day, [$($year)?]);
+
+        /// The parts of the current day, see [`registry`]($crate::template::registry).
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle,
+            parts: &[$(
+                ($part, |input: &str, is_timed: bool| {
+                    $crate::template::runner::run_part_in_process($func, input, $part, is_timed)
+                }),
+            )*],
+        };
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::input::read_or_exit(puzzle());
+            $( run_part($func, &input, puzzle(), $part); )*
+        }
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+
+    (@impl_parsed $day:expr, [$($year:expr)?], $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day, [$($year)?]);
+
+        /// The parts of the current day, see [`registry`]($crate::template::registry).
+        /// The parse step is registered as part [`PARSE_PART`]($crate::template::report::PARSE_PART).
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle,
+            parts: &[
+                ($crate::template::report::PARSE_PART, |input: &str, is_timed: bool| {
+                    $crate::template::runner::run_parse_in_process($parse, input, is_timed)
+                }),
+                $(
+                    ($part, |input: &str, is_timed: bool| {
+                        $crate::template::runner::run_parsed_part_in_process($parse, |parsed| $func(parsed), input, $part, is_timed)
+                    }),
+                )*
+            ],
+        };
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::input::read_or_exit(puzzle());
+            let parsed = run_parse($parse, &input);
+            $( run_part(|parsed| $func(parsed), &parsed, puzzle(), $part); )*
+        }
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@common $day:expr, [$($year:expr)?]) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The puzzle of the current day. Unless declared otherwise, it belongs to the default year.
+        #[allow(dead_code)]
+        fn puzzle() -> $crate::template::PuzzleId {
+            let year: Option<$crate::template::Year> = None $(.or(Some($crate::year!($year))))?;
+            year.map_or_else(
+                || $crate::template::PuzzleId::from(DAY),
+                |year| $crate::template::PuzzleId::new(year, DAY),
+            )
+        }
+
+        /// The parameters of the current run, see [`params`]($crate::template::params).
+        #[allow(dead_code)]
+        fn params() -> $crate::template::params::Params {
+            $crate::template::params::current()
+        }
+
+        /// The visualization of the current run, see [`visualize`]($crate::template::visualize).
+        #[allow(dead_code)]
+        fn visualization() -> Option<$crate::template::visualize::Visualization> {
+            $crate::template::visualize::current()
+        }
+
+        /// Runs the examples listed in the manifest of the current day, see [`manifest`]($crate::template::manifest).
+        #[cfg(test)]
+        #[test]
+        fn test_examples() {
+            $crate::template::manifest::check_examples(&SOLUTION);
+        }
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
-
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
     };
 }
@@ -0,0 +1,155 @@
+//! Puzzle parameters, i.e. values that differ between the examples and the real input of a puzzle (e.g. a grid size).
+//!
+//! Parameters are set per run: from the example manifest (see [`manifest`](crate::template::manifest)) when running
+//! examples, or with `--param <key>=<value>` when running a solution binary. Solutions read them with the `params()`
+//! function generated by [`solution!`](crate::solution) and fall back to the values of the real input:
+//!
+//! ```ignore
+//! let width = params().get_or("width", 101);
+//! ```
+
+use std::{cell::RefCell, collections::BTreeMap, env, str::FromStr};
+
+/// The parameters of a single run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// Reads the parameters from the command-line arguments, e.g. `--param width=11 --param height=7`.
+    ///
+    /// # Panics
+    /// If a parameter is not formatted as `<key>=<value>`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        args.iter()
+            .enumerate()
+            .filter(|(_, arg)| *arg == "--param")
+            .filter_map(|(i, _)| args.get(i + 1))
+            .map(|arg| parse_param(arg).unwrap_or_else(|e| panic!("{e}")))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The value of a parameter, if present.
+    ///
+    /// # Panics
+    /// If the value can not be parsed as `T`.
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.0.get(key).map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("invalid value `{value}` for parameter `{key}`"))
+        })
+    }
+
+    /// The value of a parameter, or the given default (usually the value of the real input) if not present.
+    ///
+    /// # Panics
+    /// If the value can not be parsed as `T`.
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> T {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// The parameters formatted as command-line arguments, see [`Params::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .flat_map(|(key, value)| ["--param".to_string(), format!("{key}={value}")])
+            .collect()
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Params(iter.into_iter().collect())
+    }
+}
+
+impl<const N: usize> From<[(&str, &str); N]> for Params {
+    fn from(value: [(&str, &str); N]) -> Self {
+        value
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+}
+
+/// Parses a single `<key>=<value>` parameter.
+pub fn parse_param(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "invalid parameter `{arg}`, expected the format `<key>=<value>`."
+        )),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+thread_local! {
+    static CURRENT: RefCell<Params> = RefCell::new(Params::default());
+}
+
+/// Restores the previous parameters when dropped, including when the run panicked.
+struct Restore(Params);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.0);
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// The parameters of the current run on this thread.
+pub fn current() -> Params {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Runs `f` with the given parameters set as the parameters of the current run.
+pub fn with<R>(params: &Params, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(params.clone()));
+    let _restore = Restore(previous);
+    f()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{current, parse_param, with, Params};
+
+    #[test]
+    fn reads_params() {
+        let params = Params::from([("width", "11"), ("name", "small")]);
+        assert_eq!(params.get::<u32>("width"), Some(11));
+        assert_eq!(params.get::<String>("name"), Some("small".to_string()));
+        assert_eq!(params.get_or("height", 103), 103);
+        assert_eq!(
+            params.to_args(),
+            vec!["--param", "name=small", "--param", "width=11"]
+        );
+    }
+
+    #[test]
+    fn parses_params() {
+        assert_eq!(
+            parse_param("width=11"),
+            Ok(("width".to_string(), "11".to_string()))
+        );
+        assert!(parse_param("width").is_err());
+        assert!(parse_param("=11").is_err());
+    }
+
+    #[test]
+    fn scopes_params() {
+        let params = Params::from([("width", "11")]);
+        let width = with(&params, || current().get_or("width", 101));
+        assert_eq!(width, 11);
+        assert!(current().is_empty());
+    }
+}
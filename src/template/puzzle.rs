@@ -0,0 +1,142 @@
+/// Reads the puzzle descriptions stored in `data/{year}/puzzles`, see [`aoc_client`](crate::template::aoc_client).
+use std::fs;
+
+use crate::template::PuzzleId;
+
+/// The parsed markdown of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Description {
+    /// The title of the puzzle, e.g. "Day 1: Historian Hysteria".
+    pub title: Option<String>,
+    /// The markdown of each part. The second part is only available once the first part is solved.
+    pub parts: Vec<String>,
+}
+
+impl Description {
+    /// Reads the stored description of a puzzle, if it was downloaded.
+    pub fn read(puzzle: PuzzleId) -> Option<Self> {
+        fs::read_to_string(puzzle.data_path("puzzles", "md"))
+            .ok()
+            .map(|markdown| Self::parse(&markdown))
+    }
+
+    pub fn parse(markdown: &str) -> Self {
+        let mut title = None;
+        let mut parts: Vec<String> = vec![];
+
+        for line in markdown.lines() {
+            if let Some(heading) = line.strip_prefix("## ") {
+                let heading = heading.trim().trim_matches('-').trim();
+                if title.is_none() {
+                    title = Some(heading.to_string());
+                }
+                parts.push(String::new());
+            } else if let Some(part) = parts.last_mut() {
+                part.push_str(line);
+                part.push('\n');
+            }
+        }
+
+        Description { title, parts }
+    }
+
+    /// The markdown of a part (1 or 2).
+    pub fn part(&self, part: u8) -> Option<&str> {
+        self.parts
+            .get(usize::from(part).checked_sub(1)?)
+            .map(String::as_str)
+    }
+
+    /// The code blocks of all parts, in order of appearance and without duplicates.
+    /// These are the candidates for the example inputs of a puzzle.
+    pub fn code_blocks(&self) -> Vec<String> {
+        let mut blocks: Vec<String> = vec![];
+
+        for part in &self.parts {
+            let mut block: Option<String> = None;
+
+            for line in part.lines() {
+                match (&mut block, line.starts_with("```")) {
+                    (None, true) => block = Some(String::new()),
+                    (Some(_), true) => {
+                        let code = block.take().unwrap();
+                        if !blocks.contains(&code) {
+                            blocks.push(code);
+                        }
+                    }
+                    (Some(code), false) => {
+                        code.push_str(line);
+                        code.push('\n');
+                    }
+                    (None, false) => {}
+                }
+            }
+        }
+
+        blocks
+    }
+
+    /// Guesses the answer of the example of a part.
+    /// Puzzles highlight the example's answer as emphasized code, the last one of a part is taken.
+    pub fn example_answer(&self, part: u8) -> Option<String> {
+        let text = self.part(part)?;
+        let end = text.rfind("*`")?;
+        let start = text[..end].rfind("`*")? + 2;
+        Some(text[start..end].to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Description;
+
+    const MARKDOWN: &str = "## --- Day 1: Historian Hysteria ---
+
+Pair up the numbers, e.g. `*3*` and `4`.
+
+```
+3   4
+4   3
+```
+
+In the example above, this is `*11*`.
+
+## --- Part Two ---
+
+The similarity score is `*31*`.
+";
+
+    #[test]
+    fn parses_titles_and_parts() {
+        let description = Description::parse(MARKDOWN);
+        assert_eq!(
+            description.title.as_deref(),
+            Some("Day 1: Historian Hysteria")
+        );
+        assert_eq!(description.parts.len(), 2);
+        assert!(description.part(2).unwrap().contains("similarity"));
+        assert_eq!(description.part(3), None);
+    }
+
+    #[test]
+    fn collects_code_blocks() {
+        let description = Description::parse(&format!("{MARKDOWN}\n```\n3   4\n4   3\n```\n"));
+        assert_eq!(
+            description.code_blocks(),
+            vec!["3   4\n4   3\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn guesses_example_answers() {
+        let description = Description::parse(MARKDOWN);
+        assert_eq!(description.example_answer(1), Some("11".into()));
+        assert_eq!(description.example_answer(2), Some("31".into()));
+
+        let description = Description::parse("## --- Day 2 ---\n\nNo answer.\n");
+        assert_eq!(description.example_answer(1), None);
+        assert_eq!(description.example_answer(2), None);
+    }
+}
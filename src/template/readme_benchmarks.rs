@@ -1,9 +1,9 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, path::PathBuf};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::alloc::{self, AllocStats};
+use crate::template::timings::{PartTiming, Timing, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,17 +21,13 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(super) pos_start: usize,
+    pub(super) pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates a table between two markers, also used by [`readme_stars`](super::readme_stars).
+pub(super) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,80 +48,267 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// How the days of the benchmark table are ordered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Time,
+}
+
+/// Options of the benchmark table in the readme.
+/// Configured with `AOC_BENCHMARKS_*` environment variables in `.cargo/config.toml`, see [`TableOptions::from_env`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableOptions {
+    /// Whether `cargo time --store` updates the readme at all.
+    pub enabled: bool,
+    /// A markdown file that is inserted between the header and the table.
+    pub explanation: Option<PathBuf>,
+    pub sort: SortOrder,
+    /// Adds a column with the share of each day of the total time.
+    pub show_share: bool,
+    /// Marks the slowest day in bold.
+    pub highlight_slowest: bool,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            explanation: None,
+            sort: SortOrder::Day,
+            show_share: false,
+            highlight_slowest: false,
+        }
+    }
+}
+
+impl TableOptions {
+    /// Reads the options from the environment. Options that are not set keep their default.
+    ///
+    /// - `AOC_BENCHMARKS_README`: set to `false` to not update the readme.
+    /// - `AOC_BENCHMARKS_EXPLANATION`: path of the explanation file, empty for none.
+    /// - `AOC_BENCHMARKS_SORT`: `day` or `time`.
+    /// - `AOC_BENCHMARKS_SHARE`: `true` to show the share of the total time.
+    /// - `AOC_BENCHMARKS_HIGHLIGHT_SLOWEST`: `true` to highlight the slowest day.
+    pub fn from_env() -> Self {
+        let var = |name: &str| env::var(name).ok().map(|v| v.trim().to_lowercase());
+        let flag = |name: &str, default: bool| {
+            var(name).map_or(default, |v| matches!(v.as_str(), "true" | "1" | "yes"))
+        };
+
+        let defaults = Self::default();
+
+        Self {
+            enabled: flag("AOC_BENCHMARKS_README", defaults.enabled),
+            explanation: env::var("AOC_BENCHMARKS_EXPLANATION")
+                .ok()
+                .filter(|path| !path.trim().is_empty())
+                .map(PathBuf::from),
+            sort: match var("AOC_BENCHMARKS_SORT").as_deref() {
+                Some("time") => SortOrder::Time,
+                _ => defaults.sort,
+            },
+            show_share: flag("AOC_BENCHMARKS_SHARE", defaults.show_share),
+            highlight_slowest: flag(
+                "AOC_BENCHMARKS_HIGHLIGHT_SLOWEST",
+                defaults.highlight_slowest,
+            ),
+        }
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    mut timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<String, Error> {
     let header = format!("{prefix} Benchmarks");
-    // read explanation from file BenchmarkText.md
-    let explanation = fs::read_to_string("src/template/BenchmarkText.md").unwrap();
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        explanation,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+
+    // NOTE: parse columns are only shown if a solution has a shared parse step, see `solution!`.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    // NOTE: heap columns are only shown if allocation statistics were collected, see `alloc`.
+    let has_alloc = timings
+        .data
+        .iter()
+        .any(|t| t.parts().into_iter().flatten().any(|p| p.alloc.is_some()));
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
+
+    if let Some(path) = &options.explanation {
+        lines.push(fs::read_to_string(path)?);
+    }
+
+    lines.push(String::new());
+
+    let (mut columns, mut alignment) = if has_parse {
+        (
+            "| Day | Parse | Part 1 | Part 2 |".to_string(),
+            "| :---: | :---: | :---: | :---:  |".to_string(),
+        )
+    } else {
+        (
+            "| Day | Part 1 | Part 2 |".to_string(),
+            "| :---: | :---: | :---:  |".to_string(),
+        )
+    };
+
+    if options.show_share {
+        columns.push_str(" Share |");
+        alignment.push_str(" :---: |");
+    }
+
+    if has_alloc {
+        if has_parse {
+            columns.push_str(" Parse Heap |");
+            alignment.push_str(" :---: |");
+        }
+        columns.push_str(" Part 1 Heap | Part 2 Heap |");
+        alignment.push_str(" :---: | :---: |");
+    }
+
+    lines.push(columns);
+    lines.push(alignment);
+
+    if options.sort == SortOrder::Time {
+        timings
+            .data
+            .sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let total_nanos: f64 = timings.data.iter().map(|t| t.total_nanos).sum();
+    let slowest = timings
+        .data
+        .iter()
+        .max_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos))
+        .map(Timing::puzzle);
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let path = timing.puzzle().bin_path();
+        let mut day = format!("[Day {}]({})", timing.day.into_inner(), path);
+
+        if options.highlight_slowest && slowest == Some(timing.puzzle()) {
+            day = format!("**{day}**");
+        }
+
+        let parts = shown_parts(&timing, has_parse);
+        let mut row = format!("| {day} |");
+        for part in &parts {
+            row.push_str(&format!(" {} |", format_cell(*part)));
+        }
+
+        if options.show_share {
+            let share = if total_nanos > 0.0 {
+                timing.total_nanos / total_nanos * 100.0
+            } else {
+                0.0
+            };
+            row.push_str(&format!(" {share:.1}% |"));
+        }
+
+        if has_alloc {
+            for part in &parts {
+                row.push_str(&format!(
+                    " {} |",
+                    format_alloc_cell(part.and_then(|p| p.alloc))
+                ));
+            }
+        }
+
+        lines.push(row);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
 
-    lines.join("\n")
+    Ok(lines.join("\n"))
+}
+
+/// The parse step, if shown, and the parts of a timing, in the order of the columns.
+fn shown_parts(timing: &Timing, has_parse: bool) -> Vec<Option<&PartTiming>> {
+    let [parse, part_1, part_2] = timing.parts();
+    if has_parse {
+        vec![parse, part_1, part_2]
+    } else {
+        vec![part_1, part_2]
+    }
+}
+
+fn format_cell(timing: Option<&PartTiming>) -> String {
+    let duration = format!("`{}`", timing.map_or("-", |t| t.duration.as_str()));
+    match timing.and_then(|t| t.stats) {
+        Some(stats) => format!(
+            "{duration} <sub>± {:.1?}, p95 {:.1?}</sub>",
+            stats.stddev, stats.p95
+        ),
+        None => duration,
+    }
+}
+
+fn format_alloc_cell(alloc: Option<AllocStats>) -> String {
+    match alloc {
+        Some(alloc) => format!(
+            "`{}` <sub>peak, {} allocs, {} total</sub>",
+            alloc::format_bytes(alloc.peak_bytes),
+            alloc.allocations,
+            alloc::format_bytes(alloc.bytes)
+        ),
+        None => "`-`".into(),
+    }
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", timings, total_millis, options)?;
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(timings: Timings, options: &TableOptions) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, SortOrder, TableOptions, MARKER};
+    use crate::{
+        day,
+        template::alloc::AllocStats,
+        template::runner::BenchStats,
+        template::timings::{PartTiming, Timing, Timings},
+        template::{Day, PuzzleId},
+        year,
+    };
+    use std::time::Duration;
+
+    fn timing(day: Day, part_1: &str, part_2: &str, total_nanos: f64) -> Timing {
+        Timing {
+            part_1: Some(PartTiming::new(part_1)),
+            part_2: Some(PartTiming::new(part_2)),
+            total_nanos,
+            ..Timing::new(PuzzleId::new(year!(2024), day))
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
+                timing(day!(1), "10ms", "20ms", 3e+10),
+                timing(day!(2), "30ms", "40ms", 7e+10),
+                timing(day!(4), "40ms", "50ms", 9e+10),
             ],
         }
     }
@@ -134,36 +317,104 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn format_benchmark_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().stats = Some(BenchStats {
+            mean: Duration::from_millis(10),
+            min: Duration::from_millis(9),
+            median: Duration::from_millis(10),
+            p95: Duration::from_micros(12_500),
+            stddev: Duration::from_micros(800),
+            samples: 100,
+            outliers: 0,
+        });
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` <sub>± 800.0µs, p95 12.5ms</sub> | `20ms` |"
+        ));
+    }
+
+    #[test]
+    fn format_alloc_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().alloc = Some(AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak_bytes: 1536,
+        });
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 Heap | Part 2 Heap |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.5 KiB` <sub>peak, 12 allocs, 4.0 KiB total</sub> | `-` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` |"));
+    }
+
+    #[test]
+    fn format_parse_alloc_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some(PartTiming {
+            alloc: Some(AllocStats {
+                allocations: 1,
+                bytes: 64,
+                peak_bytes: 64,
+            }),
+            ..PartTiming::new("5ms")
+        });
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+        assert!(s.contains(
+            "| Day | Parse | Part 1 | Part 2 | Parse Heap | Part 1 Heap | Part 2 Heap |"
+        ));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` | `64 B` <sub>peak, 1 allocs, 64 B total</sub> | `-` | `-` |"
+        ));
+    }
+
+    #[test]
+    fn format_parse_times() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some(PartTiming::new("2ms"));
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `2ms` | `30ms` | `40ms` |"));
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -183,4 +434,44 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_options() {
+        let options = TableOptions {
+            sort: SortOrder::Time,
+            show_share: true,
+            highlight_slowest: true,
+            ..TableOptions::default()
+        };
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Share |",
+            "| :---: | :---: | :---:  | :---: |",
+            "| **[Day 4](./src/bin/04.rs)** | `40ms` | `50ms` | 47.4% |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | 36.8% |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | 15.8% |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn errors_if_explanation_missing() {
+        let options = TableOptions {
+            explanation: Some("does-not-exist.md".into()),
+            ..TableOptions::default()
+        };
+        let mut s = format!("{}{}", MARKER, MARKER);
+        assert!(update_content(&mut s, get_mock_timings(), 190.0, &options).is_err());
+    }
 }
@@ -0,0 +1,203 @@
+/// Module that updates the readme with the ⭐️ progress of a year.
+/// The table has the same format as the one written by `aoc-readme-stars`, but is built from the local
+/// submission log and answers store instead of a private leaderboard.
+use std::{collections::BTreeMap, fs};
+
+use crate::template::answers::Answers;
+use crate::template::aoc_client::Outcome;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::submissions::Submissions;
+use crate::template::{Day, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// The solved parts of every day of a year that has at least one star.
+fn collect_stars(
+    year: Year,
+    submissions: &Submissions,
+    answers: &Answers,
+) -> BTreeMap<Day, [bool; 2]> {
+    let mut stars: BTreeMap<Day, [bool; 2]> = BTreeMap::new();
+
+    let solved_parts = submissions
+        .data
+        .iter()
+        .filter(|record| record.outcome == Outcome::Correct)
+        .map(|record| (record.year, record.day, record.part))
+        .chain(answers.data.iter().flat_map(|answer| {
+            [1, 2]
+                .into_iter()
+                .filter(|part| answer.part(*part).is_some())
+                .map(|part| (answer.year, answer.day, part))
+        }));
+
+    for (_, day, part) in solved_parts.filter(|(y, _, _)| *y == year) {
+        if let 1 | 2 = part {
+            stars.entry(day).or_default()[usize::from(part - 1)] = true;
+        }
+    }
+
+    stars
+}
+
+fn construct_table(prefix: &str, year: Year, stars: &BTreeMap<Day, [bool; 2]>) -> String {
+    let header = format!("{prefix} {year} Results");
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |solved: bool| if solved { "⭐" } else { " " };
+
+    for (day, [part_1, part_2]) in stars {
+        let day = day.into_inner();
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} |",
+            star(*part_1),
+            star(*part_2)
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    submissions: &Submissions,
+    answers: &Answers,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let stars = collect_stars(year, submissions, answers);
+    let table = construct_table("##", year, &stars);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Rebuilds the stars table of a year from the submission log and the answers store.
+pub fn update(year: Year) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let submissions = Submissions::read_from_file().map_err(Error::Parser)?;
+    let answers = Answers::read_from_file().map_err(Error::Parser)?;
+    update_content(&mut readme, year, &submissions, &answers)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::{
+        day,
+        template::{
+            answers::{Answer, Answers},
+            aoc_client::Outcome,
+            submissions::{Record, Submissions},
+        },
+        year,
+    };
+
+    fn record(day: crate::template::Day, part: u8, outcome: Outcome) -> Record {
+        Record {
+            year: year!(2024),
+            day,
+            part,
+            answer: "42".into(),
+            outcome,
+            timestamp: 1_000,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                record(day!(1), 1, Outcome::Correct),
+                record(day!(1), 2, Outcome::Correct),
+                record(day!(3), 1, Outcome::TooLow),
+                record(day!(3), 1, Outcome::Correct),
+                record(day!(4), 1, Outcome::Incorrect),
+            ],
+        }
+    }
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    year: year!(2024),
+                    day: day!(2),
+                    part_1: Some("11".into()),
+                    part_2: None,
+                },
+                Answer {
+                    year: year!(2023),
+                    day: day!(5),
+                    part_1: Some("11".into()),
+                    part_2: Some("31".into()),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(
+            &mut s,
+            year!(2024),
+            &get_mock_submissions(),
+            &get_mock_answers(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn updates_existing_stars() {
+        let mut s = format!("foo\n{}\n## 2024 Results\n{}\nbar", MARKER, MARKER);
+        update_content(
+            &mut s,
+            year!(2024),
+            &get_mock_submissions(),
+            &get_mock_answers(),
+        )
+        .unwrap();
+
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn uses_year_of_table() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            year!(2023),
+            &get_mock_submissions(),
+            &get_mock_answers(),
+        )
+        .unwrap();
+        assert!(s.contains("## 2023 Results"));
+        assert!(s.contains("| [Day 5](https://adventofcode.com/2023/day/5) | ⭐ | ⭐ |"));
+        assert!(!s.contains("Day 1"));
+    }
+}
@@ -0,0 +1,29 @@
+/// Registry of solutions that can be run in-process, i.e. without spawning a solution binary per day.
+/// Every solution declared with [`solution!`](crate::solution) exposes a `SOLUTION` constant.
+/// The build script of this crate includes all solution modules into the main binary and collects these constants.
+use crate::template::{report::PartReport, PuzzleId};
+
+/// Runs a single part against the given input. The second parameter controls whether the part is benched.
+pub type PartRunner = fn(&str, bool) -> PartReport;
+
+/// The parts of a single puzzle, keyed by their part number.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: fn() -> PuzzleId,
+    pub parts: &'static [(u8, PartRunner)],
+}
+
+impl Solution {
+    /// The runner of a part (1 or 2), if the solution implements it.
+    pub fn part(&self, part: u8) -> Option<PartRunner> {
+        self.parts
+            .iter()
+            .find(|(number, _)| *number == part)
+            .map(|(_, run_part)| *run_part)
+    }
+}
+
+/// Find the solution for a puzzle in a registry.
+pub fn find(solutions: &[Solution], puzzle: PuzzleId) -> Option<&Solution> {
+    solutions.iter().find(|s| (s.puzzle)() == puzzle)
+}
@@ -0,0 +1,305 @@
+/// Machine-readable output format of the solution binaries.
+/// When invoked with `--format json`, a solution prints one JSON object per part to stdout instead of the human-oriented result lines.
+use std::{collections::HashMap, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{alloc::AllocStats, runner::BenchStats};
+
+/// The output format of a solution binary, selected with the `--format` flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+impl OutputFormat {
+    /// Reads the output format from the command-line arguments, defaulting to [`OutputFormat::Human`].
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        match args.iter().position(|x| x == "--format") {
+            Some(i) if args.get(i + 1).is_some_and(|x| x == "json") => OutputFormat::Json,
+            _ => OutputFormat::Human,
+        }
+    }
+}
+
+/// The part number of the shared parse step of a solution, see [`run_parse`](crate::template::runner::run_parse).
+/// Its reports have no answer.
+pub const PARSE_PART: u8 = 0;
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    /// Only present if the part was benched.
+    pub stats: Option<BenchStats>,
+    /// Only present if allocation statistics are enabled, see [`alloc`](crate::template::alloc).
+    pub alloc: Option<AllocStats>,
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("stats".into(), stats_to_json(value.stats));
+        map.insert("alloc".into(), alloc_to_json(value.alloc));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let duration = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| Duration::from_nanos(v.round() as u64))
+            .ok_or("Expected report.duration_nanos to be a number.")?;
+
+        let stats = json
+            .get("stats")
+            .map_or(Ok(None), stats_from_json)
+            .map_err(|()| "Expected report.stats to be null or a stats object.")?;
+
+        let alloc = json
+            .get("alloc")
+            .map_or(Ok(None), alloc_from_json)
+            .map_err(|()| "Expected report.alloc to be null or an alloc object.")?;
+
+        Ok(PartReport {
+            part,
+            answer: answer.cloned(),
+            duration,
+            stats,
+            alloc,
+        })
+    }
+}
+
+impl FromStr for PartReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON line."))?;
+        PartReport::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const STATS_KEYS: [&str; 7] = [
+    "mean_nanos",
+    "min_nanos",
+    "median_nanos",
+    "p95_nanos",
+    "stddev_nanos",
+    "samples",
+    "outliers",
+];
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn stats_to_json(stats: Option<BenchStats>) -> JsonValue {
+    let Some(stats) = stats else {
+        return JsonValue::Null;
+    };
+
+    let values = [
+        stats.mean.as_nanos(),
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.p95.as_nanos(),
+        stats.stddev.as_nanos(),
+        stats.samples,
+        stats.outliers,
+    ];
+
+    JsonValue::Object(
+        STATS_KEYS
+            .iter()
+            .zip(values)
+            .map(|(key, value)| ((*key).to_string(), JsonValue::Number(value as f64)))
+            .collect(),
+    )
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn stats_from_json(value: &JsonValue) -> Result<Option<BenchStats>, ()> {
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    let json = value.get::<HashMap<String, JsonValue>>().ok_or(())?;
+    let mut values = [0_u64; STATS_KEYS.len()];
+
+    for (key, value) in STATS_KEYS.iter().zip(values.iter_mut()) {
+        *value = json
+            .get(*key)
+            .and_then(|v| v.get::<f64>())
+            .ok_or(())?
+            .round() as u64;
+    }
+
+    let [mean, min, median, p95, stddev, samples, outliers] = values;
+
+    Ok(Some(BenchStats {
+        mean: Duration::from_nanos(mean),
+        min: Duration::from_nanos(min),
+        median: Duration::from_nanos(median),
+        p95: Duration::from_nanos(p95),
+        stddev: Duration::from_nanos(stddev),
+        samples: u128::from(samples),
+        outliers: u128::from(outliers),
+    }))
+}
+
+const ALLOC_KEYS: [&str; 3] = ["allocations", "bytes", "peak_bytes"];
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn alloc_to_json(alloc: Option<AllocStats>) -> JsonValue {
+    let Some(alloc) = alloc else {
+        return JsonValue::Null;
+    };
+
+    let values = [alloc.allocations, alloc.bytes, alloc.peak_bytes];
+
+    JsonValue::Object(
+        ALLOC_KEYS
+            .iter()
+            .zip(values)
+            .map(|(key, value)| ((*key).to_string(), JsonValue::Number(value as f64)))
+            .collect(),
+    )
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn alloc_from_json(value: &JsonValue) -> Result<Option<AllocStats>, ()> {
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    let json = value.get::<HashMap<String, JsonValue>>().ok_or(())?;
+    let mut values = [0_u64; ALLOC_KEYS.len()];
+
+    for (key, value) in ALLOC_KEYS.iter().zip(values.iter_mut()) {
+        *value = json
+            .get(*key)
+            .and_then(|v| v.get::<f64>())
+            .ok_or(())?
+            .round() as u64;
+    }
+
+    let [allocations, bytes, peak_bytes] = values;
+
+    Ok(Some(AllocStats {
+        allocations,
+        bytes,
+        peak_bytes,
+    }))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PartReport;
+    use crate::template::{alloc::AllocStats, runner::BenchStats};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn roundtrip(report: &PartReport) -> PartReport {
+        let line = JsonValue::from(report).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        line.parse().unwrap()
+    }
+
+    #[test]
+    fn roundtrips_answers_with_special_characters() {
+        let report = PartReport {
+            part: 1,
+            answer: Some("@ ( ) 2s @ 5 samples)\nline two".into()),
+            duration: Duration::from_nanos(74_130),
+            stats: None,
+            alloc: None,
+        };
+        assert_eq!(roundtrip(&report), report);
+    }
+
+    #[test]
+    fn roundtrips_missing_answers() {
+        let report = PartReport {
+            part: 2,
+            answer: None,
+            duration: Duration::from_nanos(10),
+            stats: None,
+            alloc: None,
+        };
+        assert_eq!(roundtrip(&report), report);
+    }
+
+    #[test]
+    fn roundtrips_stats() {
+        let report = PartReport {
+            part: 2,
+            answer: Some("42".into()),
+            duration: Duration::from_millis(3),
+            stats: Some(BenchStats {
+                mean: Duration::from_millis(3),
+                min: Duration::from_micros(2_500),
+                median: Duration::from_micros(2_900),
+                p95: Duration::from_micros(3_800),
+                stddev: Duration::from_micros(120),
+                samples: 330,
+                outliers: 4,
+            }),
+            alloc: Some(AllocStats {
+                allocations: 12,
+                bytes: 4096,
+                peak_bytes: 1024,
+            }),
+        };
+        assert_eq!(roundtrip(&report), report);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_human_output() {
+        "Part 1: 42 (1.0ms @ 10 samples)"
+            .parse::<PartReport>()
+            .unwrap();
+    }
+}
@@ -1,41 +1,230 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    io,
+    path::Path,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{input, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    registry::{self, Solution},
+    report::PartReport,
+    runner::print_report,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// How the run of a single day ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    Ok,
+    /// The day was stopped after exceeding the timeout of the executor.
+    TimedOut,
+    Panicked,
+    /// The solution binary of the day failed to compile.
+    BuildFailed,
+    /// The input of the day is missing or empty, see [`input`](crate::template::input).
+    InvalidInput,
+    /// The day has not been scaffolded or did not report any part.
+    NotSolved,
+}
 
-    let mut need_space = false;
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayStatus::Ok => write!(f, "ok"),
+            DayStatus::TimedOut => write!(f, "timed out"),
+            DayStatus::Panicked => write!(f, "panicked"),
+            DayStatus::BuildFailed => write!(f, "build failed"),
+            DayStatus::InvalidInput => write!(f, "invalid input"),
+            DayStatus::NotSolved => write!(f, "not solved"),
+        }
+    }
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// Results of running a set of days.
+pub struct MultiRun {
+    /// Reports of every day that has been scaffolded, sorted by day.
+    /// Days that timed out or panicked only include the parts that finished.
+    pub reports: Vec<(PuzzleId, Vec<PartReport>)>,
+    /// How the run of every day ended, sorted by day.
+    pub statuses: Vec<(PuzzleId, DayStatus)>,
+    /// Only present if the days were benched. Only includes days that finished without errors.
+    pub timings: Option<Timings>,
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+/// Determines how the solutions of a set of days are executed.
+#[derive(Clone, Copy)]
+pub enum Executor<'a> {
+    /// Every day runs in its own solution binary.
+    /// With `jobs > 1`, all solutions are built once upfront and then run concurrently.
+    /// The output of each day is buffered, so the per-day blocks are still printed in order.
+    /// With `alloc_stats`, the binaries are built with the `alloc-stats` feature, see [`alloc`](crate::template::alloc).
+    /// With a `timeout`, the binary of a day is killed once it runs longer than the timeout.
+    Binaries {
+        is_release: bool,
+        jobs: usize,
+        alloc_stats: bool,
+        timeout: Option<Duration>,
+    },
+    /// Every day runs in the current process, using the solutions of the registry.
+    /// Every day runs on its own thread, which is abandoned once it runs longer than the `timeout`.
+    /// An abandoned day keeps using the CPU, so benchmarks of later days should be taken with a grain of salt.
+    InProcess {
+        solutions: &'a [Solution],
+        timeout: Option<Duration>,
+    },
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+impl<'a> Executor<'a> {
+    /// Runs the days in-process if a registry is passed, in their solution binaries otherwise.
+    pub fn new(is_release: bool, jobs: usize, registry: Option<&'a [Solution]>) -> Self {
+        match registry {
+            Some(solutions) => Executor::InProcess {
+                solutions,
+                timeout: None,
+            },
+            None => Executor::Binaries {
+                is_release,
+                jobs,
+                alloc_stats: false,
+                timeout: None,
+            },
+        }
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
+    /// Collects allocation statistics when running solution binaries.
+    /// In-process runs use the allocator of the current binary and collect them if it was built with `alloc-stats`.
+    #[must_use]
+    pub fn with_alloc_stats(mut self, alloc_stats: bool) -> Self {
+        if let Executor::Binaries {
+            alloc_stats: value, ..
+        } = &mut self
+        {
+            *value = alloc_stats;
+        }
+        self
+    }
+
+    /// Stops days that run longer than `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        match &mut self {
+            Executor::Binaries { timeout: value, .. }
+            | Executor::InProcess { timeout: value, .. } => {
+                *value = timeout;
+            }
+        }
+        self
+    }
+}
+
+/// Run a set of days of a year and print their results, sorted by day.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_timed: bool,
+    executor: Executor,
+) -> MultiRun {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<PuzzleId> = all_days()
+        .filter(|day| days_to_run.contains(day))
+        .map(|day| PuzzleId::new(year, day))
+        .collect();
+    let mut collector = Collector::with_capacity(days.len());
+
+    match executor {
+        Executor::Binaries {
+            is_release,
+            jobs,
+            alloc_stats,
+            timeout,
+        } if jobs <= 1 => {
+            // NOTE: runs with a timeout invoke the built binaries directly, see `run_solution`.
+            let build_failures = if timeout.is_some() {
+                build_solutions(&days, is_release, alloc_stats)
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                HashSet::new()
+            };
+
+            for day in days {
+                collector.print_header(day);
+                let output = if build_failures.contains(&day) {
+                    child_commands::SolutionOutput::new(DayStatus::BuildFailed)
+                } else {
+                    child_commands::run_solution(
+                        day,
+                        is_timed,
+                        is_release,
+                        alloc_stats,
+                        false,
+                        timeout,
+                    )
+                    .unwrap()
+                };
+                collector.collect(day, output.reports, output.status, timeout);
             }
-        });
+        }
+        Executor::Binaries {
+            is_release,
+            jobs,
+            alloc_stats,
+            timeout,
+        } => {
+            let build_failures = build_solutions(&days, is_release, alloc_stats);
+
+            run_parallel(
+                &days,
+                jobs,
+                |day| {
+                    if build_failures.contains(&day) {
+                        return child_commands::SolutionOutput::new(DayStatus::BuildFailed);
+                    }
+
+                    child_commands::run_solution(
+                        day,
+                        is_timed,
+                        is_release,
+                        alloc_stats,
+                        true,
+                        timeout,
+                    )
+                    .unwrap()
+                },
+                |day, output| {
+                    collector.print_header(day);
+                    output.stdout.iter().for_each(|line| println!("{line}"));
+                    output.stderr.iter().for_each(|line| eprintln!("{line}"));
+                    collector.collect(day, output.reports, output.status, timeout);
+                },
+            );
+        }
+        Executor::InProcess { solutions, timeout } => {
+            for day in days {
+                collector.print_header(day);
+                let (reports, status) = registry::find(solutions, day)
+                    .map_or((vec![], DayStatus::NotSolved), |solution| {
+                        run_in_process(*solution, is_timed, timeout)
+                    });
+                collector.collect(day, reports, status, timeout);
+            }
+        }
+    }
+
+    collector.print_summary();
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = if is_timed {
+        let timings = Timings {
+            data: collector.timings,
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -43,6 +232,241 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        reports: collector.reports,
+        statuses: collector.statuses,
+        timings,
+    }
+}
+
+/// Builds the solution bins upfront and returns the days that failed to compile.
+/// If building all bins at once fails, the scaffolded days are built one by one to find the broken ones.
+fn build_solutions(days: &[PuzzleId], is_release: bool, alloc_stats: bool) -> HashSet<PuzzleId> {
+    let exit = |e: Error| -> ! {
+        eprintln!("Failed to build solutions: {e:?}");
+        process::exit(1);
+    };
+
+    match child_commands::build_solutions(is_release, alloc_stats) {
+        Ok(()) => HashSet::new(),
+        Err(Error::BuildFailed) => days
+            .iter()
+            .copied()
+            .filter(|day| Path::new(&day.bin_path()).exists())
+            .filter(
+                |day| match child_commands::build_solution(*day, is_release, alloc_stats) {
+                    Ok(()) => false,
+                    Err(Error::BuildFailed) => true,
+                    Err(e) => exit(e),
+                },
+            )
+            .collect(),
+        Err(e) => exit(e),
+    }
+}
+
+/// Runs the parts of a solution on a separate thread, so that a panicking day does not abort the run.
+/// A day that exceeds the timeout is abandoned and keeps running in the background until the process exits.
+fn run_in_process(
+    solution: Solution,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> (Vec<PartReport>, DayStatus) {
+    let input = match input::read((solution.puzzle)(), false) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return (vec![], DayStatus::InvalidInput);
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    let handle = thread::spawn(move || {
+        for (_, run_part) in solution.parts {
+            if sender.send(run_part(&input, is_timed)).is_err() {
+                break;
+            }
+        }
+    });
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let mut reports = vec![];
+
+    loop {
+        let received = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match received {
+            Ok(report) => {
+                print_report(&report);
+                reports.push(report);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                // NOTE: threads can not be stopped, the abandoned day competes with the days after it.
+                if is_timed {
+                    eprintln!(
+                        "Warning: the day keeps running in the background, benchmarks of the following days may be slower."
+                    );
+                }
+                return (reports, DayStatus::TimedOut);
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    let status = match handle.join() {
+        Err(_) => DayStatus::Panicked,
+        Ok(()) if reports.is_empty() => DayStatus::NotSolved,
+        Ok(()) => DayStatus::Ok,
+    };
+
+    (reports, status)
+}
+
+/// Accumulates the results of days while they are printed.
+struct Collector {
+    need_space: bool,
+    timings: Vec<Timing>,
+    reports: Vec<(PuzzleId, Vec<PartReport>)>,
+    statuses: Vec<(PuzzleId, DayStatus)>,
+}
+
+impl Collector {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            need_space: false,
+            timings: Vec::with_capacity(capacity),
+            reports: Vec::with_capacity(capacity),
+            statuses: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn print_header(&mut self, puzzle: PuzzleId) {
+        if self.need_space {
+            println!();
+        }
+        self.need_space = true;
+
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+        println!("------");
+    }
+
+    fn collect(
+        &mut self,
+        day: PuzzleId,
+        reports: Vec<PartReport>,
+        status: DayStatus,
+        timeout: Option<Duration>,
+    ) {
+        match status {
+            DayStatus::Ok => {}
+            DayStatus::TimedOut => println!("Timed out after {:?}.", timeout.unwrap_or_default()),
+            DayStatus::Panicked => println!("Panicked."),
+            DayStatus::BuildFailed => println!("Build failed."),
+            DayStatus::InvalidInput => println!("Invalid input."),
+            DayStatus::NotSolved => println!("Not solved."),
+        }
+
+        // NOTE: partial timings of days that did not finish would overwrite complete stored timings.
+        if status == DayStatus::Ok {
+            self.timings
+                .push(child_commands::timing_from_reports(&reports, day));
+        }
+
+        if !reports.is_empty() {
+            self.reports.push((day, reports));
+        }
+
+        self.statuses.push((day, status));
+    }
+
+    fn print_summary(&self) {
+        println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+        println!("-------");
+
+        for (day, status) in &self.statuses {
+            println!("Day {}: {status}", day.day);
+        }
+    }
+}
+
+/// Run `func` for every day on up to `jobs` worker threads.
+/// `on_result` is called on the current thread in the order of `days`, as soon as a result and all results before it are available.
+fn run_parallel<D: Copy + Sync, R: Send>(
+    days: &[D],
+    jobs: usize,
+    func: impl Fn(D) -> R + Sync,
+    mut on_result: impl FnMut(D, R),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let (next, func) = (&next, &func);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(index) else {
+                    break;
+                };
+                if sender.send((index, func(*day))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // drop the original sender so that the receiver terminates once all workers are done.
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (index, result) in receiver {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&next_to_print) {
+                on_result(days[next_to_print], result);
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::run_parallel;
+    use crate::template::{all_days, Day};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn runs_in_parallel_and_reports_in_order() {
+        let days: Vec<Day> = all_days().collect();
+        let mut seen = vec![];
+
+        run_parallel(
+            &days,
+            4,
+            |day| {
+                // finish later days first to scramble the completion order.
+                thread::sleep(Duration::from_millis(u64::from(25 - day.into_inner())));
+                day.into_inner() * 2
+            },
+            |day, result| {
+                assert_eq!(result, day.into_inner() * 2);
+                seen.push(day);
+            },
+        );
+
+        assert_eq!(seen, days);
     }
 }
 
@@ -50,6 +474,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed,
     IO(io::Error),
 }
 
@@ -59,199 +484,391 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{DayStatus, Error};
+    use crate::template::{
+        input,
+        report::{PartReport, PARSE_PART},
+        runner::format_report,
+        timings::{PartTiming, Timing},
+        PuzzleId,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        env::{self, consts::EXE_SUFFIX},
+        io::{self, BufRead, BufReader},
+        path::{Path, PathBuf},
+        process::{Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Output of a solution bin.
+    pub struct SolutionOutput {
+        pub reports: Vec<PartReport>,
+        /// Only populated for buffered runs. Reports are included in their human-readable form.
+        pub stdout: Vec<String>,
+        /// Only populated for buffered runs.
+        pub stderr: Vec<String>,
+        pub status: DayStatus,
+    }
+
+    impl SolutionOutput {
+        /// The output of a day that did not run.
+        pub fn new(status: DayStatus) -> Self {
+            Self {
+                reports: vec![],
+                stdout: vec![],
+                stderr: vec![],
+                status,
+            }
         }
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Build all solution bins at once, so that they can be invoked directly by buffered runs.
+    pub fn build_solutions(is_release: bool, alloc_stats: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
 
         if is_release {
             args.push("--release");
         }
 
+        if alloc_stats {
+            args.extend(["--features", "alloc-stats"]);
+        }
+
+        let status = Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::BuildFailed)
+        }
+    }
+
+    /// Build the solution bin of a single puzzle.
+    pub fn build_solution(
+        puzzle: PuzzleId,
+        is_release: bool,
+        alloc_stats: bool,
+    ) -> Result<(), Error> {
+        let bin_name = puzzle.bin_name();
+        let mut args = vec!["build", "--quiet", "--bin", &bin_name];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        if alloc_stats {
+            args.extend(["--features", "alloc-stats"]);
+        }
+
+        let status = Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::BuildFailed)
+        }
+    }
+
+    fn get_path_for_executable(puzzle: PuzzleId, is_release: bool) -> PathBuf {
+        let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+        let profile = if is_release { "release" } else { "debug" };
+        Path::new(&target_dir)
+            .join(profile)
+            .join(format!("{}{EXE_SUFFIX}", puzzle.bin_name()))
+    }
+
+    /// Run the solution bin for a given puzzle.
+    ///
+    /// The executable is invoked directly, so that build failures are told apart from panics and killing it does not
+    /// leave the bin running behind `cargo run`.
+    /// Unbuffered runs build the bin first and forward its output while it is running.
+    /// Buffered runs and runs with a `timeout` use the executables built by [`build_solutions`] and buffered runs
+    /// capture the output.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+        alloc_stats: bool,
+        is_buffered: bool,
+        timeout: Option<Duration>,
+    ) -> Result<SolutionOutput, Error> {
+        let mut output = SolutionOutput::new(DayStatus::NotSolved);
+
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(output);
+        }
+
+        if !is_buffered && timeout.is_none() {
+            match build_solution(puzzle, is_release, alloc_stats) {
+                Err(Error::BuildFailed) => {
+                    output.status = DayStatus::BuildFailed;
+                    return Ok(output);
+                }
+                result => result?,
+            }
+        }
+
+        let mut cmd = Command::new(get_path_for_executable(puzzle, is_release));
+
+        // request machine-readable reports from the child.
+        cmd.args(["--format", "json"]);
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            cmd.arg("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward (or buffer) output to stdout/stderr while collecting the reports from stdout.
+        // both are read on separate threads, so that the child can be killed while it is still running.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stderr_thread = thread::spawn(move || {
+            stderr
+                .lines()
+                .map(Result::unwrap)
+                .filter_map(|line| {
+                    if is_buffered {
+                        Some(line)
+                    } else {
+                        eprintln!("{line}");
+                        None
+                    }
+                })
+                .collect()
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stdout_thread = thread::spawn(move || {
+            let mut reports = vec![];
+            let mut lines = vec![];
+
+            for line in stdout.lines() {
+                let line = line.unwrap();
+                // any other output of the solution (e.g. debug prints) is forwarded as-is.
+                let line = match line.parse::<PartReport>() {
+                    Ok(report) => {
+                        let formatted = format_report(&report);
+                        reports.push(report);
+                        formatted
+                    }
+                    Err(_) => line,
+                };
+
+                if is_buffered {
+                    lines.push(line);
+                } else {
+                    println!("{line}");
+                }
+            }
+
+            (reports, lines)
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let exit_status = wait_with_timeout(&mut cmd, timeout)?;
+
+        (output.reports, output.stdout) = stdout_thread.join().unwrap();
+        output.stderr = stderr_thread.join().unwrap();
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        output.status = match exit_status {
+            None => DayStatus::TimedOut,
+            Some(status) if status.code() == Some(input::INVALID_INPUT_EXIT_CODE) => {
+                DayStatus::InvalidInput
+            }
+            Some(status) if !status.success() => DayStatus::Panicked,
+            Some(_) if output.reports.is_empty() => DayStatus::NotSolved,
+            Some(_) => DayStatus::Ok,
+        };
 
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Wait for a child to exit. Kills the child and returns `None` if it runs longer than `timeout`.
+    fn wait_with_timeout(
+        child: &mut Child,
+        timeout: Option<Duration>,
+    ) -> io::Result<Option<ExitStatus>> {
+        let Some(timeout) = timeout else {
+            return child.wait().map(Some);
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        let start = Instant::now();
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
 
-                timings.total_nanos += nanos;
-            });
+            if start.elapsed() >= timeout {
+                child.kill()?;
+                child.wait()?;
+                return Ok(None);
+            }
 
-        timings
+            thread::sleep(Duration::from_millis(10));
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Collect the timings of all benched parts of a day.
+    pub fn timing_from_reports(reports: &[PartReport], puzzle: PuzzleId) -> Timing {
+        let mut timing = Timing::new(puzzle);
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+        for report in reports.iter().filter(|r| r.stats.is_some()) {
+            let part_timing = Some(PartTiming {
+                duration: format!("{:.1?}", report.duration),
+                stats: report.stats,
+                alloc: report.alloc,
+            });
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+            match report.part {
+                PARSE_PART => timing.parse = part_timing,
+                1 => timing.part_1 = part_timing,
+                2 => timing.part_2 = part_timing,
+                _ => continue,
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timing.total_nanos += report.duration.as_nanos() as f64;
+            }
+        }
+
+        timing
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{timing_from_reports, wait_with_timeout};
+        use std::{process::Command, time::Duration};
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                report::{PartReport, PARSE_PART},
+                runner::BenchStats,
+                PuzzleId,
+            },
+            year,
+        };
+
+        fn stats(mean: Duration) -> Option<BenchStats> {
+            Some(BenchStats {
+                mean,
+                min: mean,
+                median: mean,
+                p95: mean,
+                stddev: Duration::ZERO,
+                samples: 10,
+                outliers: 0,
+            })
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    PartReport {
+                        part: 1,
+                        answer: Some("@ @ @ ( ) ms (2s @ 5 samples)".into()),
+                        duration: Duration::from_nanos(74_130),
+                        stats: stats(Duration::from_nanos(74_130)),
+                        alloc: None,
+                    },
+                    PartReport {
+                        part: 2,
+                        answer: Some("10".into()),
+                        duration: Duration::from_millis(74),
+                        stats: stats(Duration::from_millis(74)),
+                        alloc: None,
+                    },
                 ],
-                day!(1),
+                PuzzleId::new(year!(2024), day!(1)),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_074_130_f64);
+            let part_1 = res.part_1.unwrap();
+            assert_eq!(part_1.duration, "74.1µs");
+            assert_eq!(part_1.stats, stats(Duration::from_nanos(74_130)));
+            assert_eq!(res.part_2.unwrap().duration, "74.0ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_parse_times() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    PartReport {
+                        part: PARSE_PART,
+                        answer: None,
+                        duration: Duration::from_millis(1),
+                        stats: stats(Duration::from_millis(1)),
+                        alloc: None,
+                    },
+                    PartReport {
+                        part: 1,
+                        answer: Some("1".into()),
+                        duration: Duration::from_millis(2),
+                        stats: stats(Duration::from_millis(2)),
+                        alloc: None,
+                    },
                 ],
-                day!(1),
+                PuzzleId::new(year!(2024), day!(1)),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 3_000_000_f64);
+            assert_eq!(res.parse.unwrap().duration, "1.0ms");
+            assert_eq!(res.part_1.unwrap().duration, "2.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn skips_parts_that_were_not_benched() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    PartReport {
+                        part: 1,
+                        answer: Some("1".into()),
+                        duration: Duration::from_millis(2),
+                        stats: None,
+                        alloc: None,
+                    },
+                    PartReport {
+                        part: 2,
+                        answer: None,
+                        duration: Duration::from_millis(2),
+                        stats: stats(Duration::from_millis(2)),
+                        alloc: None,
+                    },
                 ],
-                day!(1),
+                PuzzleId::new(year!(2024), day!(1)),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.total_nanos, 2_000_000_f64);
+            assert!(res.part_1.is_none());
+            assert_eq!(res.part_2.unwrap().duration, "2.0ms");
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn kills_children_after_timeout() {
+            let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+            let status = wait_with_timeout(&mut child, Some(Duration::from_millis(50))).unwrap();
+            assert!(status.is_none());
+
+            let mut child = Command::new("true").spawn().unwrap();
+            let status = wait_with_timeout(&mut child, Some(Duration::from_secs(5))).unwrap();
+            assert!(status.is_some_and(|status| status.success()));
         }
     }
 }
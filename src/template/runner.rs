@@ -2,61 +2,250 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use tinyjson::JsonValue;
+
+use crate::template::alloc::{self, AllocStats};
+use crate::template::aoc_client::Outcome;
+use crate::template::params::{self, Params};
+use crate::template::report::{OutputFormat, PartReport, PARSE_PART};
+use crate::template::submissions::{self, Record, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, readme_stars, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
+    let format = OutputFormat::from_args();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let is_timed = env::args().any(|x| x == "--time");
+    let params = Params::from_args();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (result, duration, stats, alloc) = params::with(&params, || {
+        run_timed(func, input, format, is_timed, |result| {
+            if format == OutputFormat::Human {
+                print_result(result, &part_str, "");
+            }
+        })
+    });
+
+    match format {
+        OutputFormat::Human => print_result(
+            &result,
+            &part_str,
+            &format_duration(&duration, stats.as_ref(), alloc.as_ref()),
+        ),
+        OutputFormat::Json => {
+            let report = PartReport {
+                part,
+                answer: result.as_ref().map(ToString::to_string),
+                duration,
+                stats,
+                alloc,
+            };
+            println!("{}", JsonValue::from(&report).stringify().unwrap());
+        }
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, puzzle, part);
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run the shared parse step of a solution, see [`solution!`](crate::solution).
+/// The step is timed and reported like a part (with the part number [`PARSE_PART`]), the parsed input is returned.
+pub fn run_parse<P>(parse: impl Fn(&str) -> P, input: &str) -> P {
+    let format = OutputFormat::from_args();
+    let is_timed = env::args().any(|x| x == "--time");
+    let params = Params::from_args();
+
+    let (parsed, duration, stats, alloc) = params::with(&params, || {
+        run_timed(&parse, input, format, is_timed, |_| {
+            if format == OutputFormat::Human {
+                print!("Parse:");
+            }
+        })
+    });
+
+    let report = PartReport {
+        part: PARSE_PART,
+        answer: None,
+        duration,
+        stats,
+        alloc,
+    };
+
+    match format {
+        OutputFormat::Human => print_report(&report),
+        OutputFormat::Json => println!("{}", JsonValue::from(&report).stringify().unwrap()),
+    }
+
+    parsed
+}
+
+/// Run the shared parse step of a solution from within another process, see [`run_part_in_process`].
+pub fn run_parse_in_process<P>(
+    parse: impl Fn(&str) -> P,
+    input: &str,
+    is_timed: bool,
+) -> PartReport {
+    let (_, duration, stats, alloc) =
+        run_timed(&parse, input, OutputFormat::Human, is_timed, |_| {
+            print!("Parse:");
+        });
+
+    PartReport {
+        part: PARSE_PART,
+        answer: None,
+        duration,
+        stats,
+        alloc,
+    }
+}
+
+/// Run a solution part that operates on the output of a shared parse step from within another process.
+/// The input is parsed upfront, so that only the part itself is timed.
+pub fn run_parsed_part_in_process<P, T: Display>(
+    parse: impl Fn(&str) -> P,
+    func: impl Fn(&P) -> Option<T>,
+    input: &str,
+    part: u8,
+    is_timed: bool,
+) -> PartReport {
+    let parsed = parse(input);
+    run_part_in_process(func, &parsed, part, is_timed)
+}
+
+/// Run a solution part from within another process, see [`registry`](crate::template::registry).
+/// Intermediate results are printed like in [`run_part`], the final result is left to the caller.
+pub fn run_part_in_process<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    part: u8,
+    is_timed: bool,
+) -> PartReport {
+    let part_str = format!("Part {part}");
+
+    let (result, duration, stats, alloc) =
+        run_timed(func, input, OutputFormat::Human, is_timed, |result| {
+            print_result(result, &part_str, "");
+        });
+
+    PartReport {
+        part,
+        answer: result.as_ref().map(ToString::to_string),
+        duration,
+        stats,
+        alloc,
+    }
+}
+
+/// Prints the final result of a report, overwriting the intermediate result printed by [`run_part_in_process`].
+pub fn print_report(report: &PartReport) {
+    print!("\r");
+    println!("{}", format_report(report));
+}
+
+/// Formats a report received from a solution binary the same way [`run_part`] prints its results.
+pub fn format_report(report: &PartReport) -> String {
+    if report.part == PARSE_PART {
+        return format!(
+            "Parse:{}",
+            format_duration(
+                &report.duration,
+                report.stats.as_ref(),
+                report.alloc.as_ref(),
+            )
+        );
+    }
+
+    format_result(
+        &report.answer,
+        &format!("Part {}", report.part),
+        &format_duration(
+            &report.duration,
+            report.stats.as_ref(),
+            report.alloc.as_ref(),
+        ),
+    )
+}
+
+/// Summary statistics over the samples collected by [`bench`].
+/// Durations are computed after outliers have been rejected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    pub samples: u128,
+    pub outliers: u128,
+}
+
+/// Run a solution part. The behavior differs depending on whether the part is timed:
+///  1. if not, the function is executed once.
+///  2. if it is, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Heap allocations are measured during the first execution, see [`alloc`].
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    format: OutputFormat,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<BenchStats>, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, alloc) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed {
+        let stats = bench(func, input, format, &base_time);
+        (result, stats.mean, Some(stats), alloc)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None, alloc)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Minimum time spent warming up caches and branch predictors before samples are collected.
+const WARMUP_DURATION: Duration = Duration::from_millis(100);
+const WARMUP_MIN_ITERATIONS: u32 = 3;
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Samples further than this many interquartile ranges outside the quartiles are rejected as outliers.
+const OUTLIER_IQR_FACTOR: f64 = 1.5;
+
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    format: OutputFormat,
+    base_time: &Duration,
+) -> BenchStats {
+    if format == OutputFormat::Human {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let warmup_timer = Instant::now();
+    let mut warmup_iterations = 0;
+    while warmup_iterations < WARMUP_MIN_ITERATIONS || warmup_timer.elapsed() < WARMUP_DURATION {
+        black_box(func(black_box(input.clone())));
+        warmup_iterations += 1;
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
@@ -71,11 +260,48 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    compute_stats(timers)
+}
+
+/// Rejects outliers using Tukey's fences and summarizes the remaining samples.
+fn compute_stats(mut samples: Vec<Duration>) -> BenchStats {
+    samples.sort_unstable();
+
+    let q1 = percentile(&samples, 25.0).as_nanos() as f64;
+    let q3 = percentile(&samples, 75.0).as_nanos() as f64;
+    let fence = (q3 - q1) * OUTLIER_IQR_FACTOR;
+    let (lower, upper) = (q1 - fence, q3 + fence);
+
+    let total = samples.len();
+    samples.retain(|x| {
+        let nanos = x.as_nanos() as f64;
+        nanos >= lower && nanos <= upper
+    });
+
+    let mean = average_duration(&samples);
+    let variance = samples
+        .iter()
+        .map(|x| (x.as_nanos() as f64 - mean as f64).powi(2))
+        .sum::<f64>()
+        / samples.len() as f64;
+
+    #[allow(clippy::cast_possible_truncation)]
+    BenchStats {
+        mean: Duration::from_nanos(mean as u64),
+        min: samples[0],
+        median: percentile(&samples, 50.0),
+        p95: percentile(&samples, 95.0),
+        stddev: Duration::from_nanos(variance.sqrt() as u64),
+        samples: samples.len() as u128,
+        outliers: (total - samples.len()) as u128,
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -86,57 +312,63 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(
+    duration: &Duration,
+    stats: Option<&BenchStats>,
+    alloc: Option<&AllocStats>,
+) -> String {
+    let alloc = alloc.map_or_else(String::new, |alloc| {
+        format!(
+            ", {} allocs, {} total, {} peak",
+            alloc.allocations,
+            alloc::format_bytes(alloc.bytes),
+            alloc::format_bytes(alloc.peak_bytes)
+        )
+    });
+
+    match stats {
+        None => format!(" ({duration:.1?}{alloc})"),
+        Some(stats) => format!(
+            " ({duration:.1?} @ {} samples, min {:.1?}, median {:.1?}, p95 {:.1?}, stddev {:.1?}, outliers {}{alloc})",
+            stats.samples, stats.min, stats.median, stats.p95, stats.stddev, stats.outliers
+        ),
     }
 }
 
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+    if !duration_str.is_empty() {
+        print!("\r");
+        println!("{}", format_result(result, part, duration_str));
+        return;
+    }
 
+    // intermediate result, printed while the part is benched.
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+        Some(result) if result.to_string().contains('\n') => print!("{part}: ▼ "),
+        Some(result) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        None => print!("{part}: ✖"),
+    }
+}
+
+fn format_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) -> String {
+    match result {
+        Some(result) if result.to_string().contains('\n') => {
+            format!("{part}: ▼ {duration_str}\n{result}")
         }
+        Some(result) => format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}"),
+        None => format!("{part}: ✖             "),
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session token is configured.
+///  3. the answer is not known to be wrong from previous submissions, see [`submissions`].
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_client::Submission, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +391,94 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if let Err(e) = aoc_client::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    let mut submissions = match Submissions::read_from_file() {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Not submitting `{answer}`: {e}");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = submissions.check(puzzle, part, &answer, submissions::now()) {
+        eprintln!("Not submitting `{answer}`: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let submission = aoc_client::submit(puzzle, part, &answer);
+
+    match &submission {
+        Ok(submission) => {
+            println!("{}", submission.message.trim_end());
+
+            submissions.push(Record {
+                year: puzzle.year,
+                day: puzzle.day,
+                part,
+                answer,
+                outcome: submission.outcome.clone(),
+                timestamp: submissions::now(),
+            });
+
+            if let Err(e) = submissions.store_file() {
+                eprintln!("failed to store submission: {e}");
+            }
+
+            // NOTE: the readme only lists the stars of the default year.
+            if submission.outcome == Outcome::Correct && puzzle.year.is_default() {
+                if let Err(e) = readme_stars::update(puzzle.year) {
+                    eprintln!("failed to update stars in the readme: {e:?}");
+                }
+            }
+        }
+        Err(e) => eprintln!("failed to submit result: {e}"),
+    }
+
+    Some(submission)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compute_stats, percentile};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_percentiles() {
+        let samples = nanos(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(percentile(&samples, 0.0), Duration::from_nanos(1));
+        assert_eq!(percentile(&samples, 50.0), Duration::from_nanos(5));
+        assert_eq!(percentile(&samples, 95.0), Duration::from_nanos(10));
+        assert_eq!(percentile(&samples, 100.0), Duration::from_nanos(10));
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = compute_stats(nanos(&[14, 10, 12, 12, 16, 12, 10, 14, 12, 8]));
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min, Duration::from_nanos(8));
+        assert_eq!(stats.median, Duration::from_nanos(12));
+        assert_eq!(stats.p95, Duration::from_nanos(16));
+        assert_eq!(stats.mean, Duration::from_nanos(12));
+        assert_eq!(stats.stddev, Duration::from_nanos(2));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = compute_stats(nanos(&[10, 11, 10, 12, 11, 10, 11, 12, 10, 5000]));
+        assert_eq!(stats.samples, 9);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.p95, Duration::from_nanos(12));
+        assert_eq!(stats.mean, Duration::from_nanos(10));
+    }
 }
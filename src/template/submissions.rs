@@ -0,0 +1,399 @@
+/// Log of submitted answers, used to refuse submissions that are already known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::Outcome;
+use crate::template::{Day, PuzzleId, Year};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// A single submitted answer and the server's verdict.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub year: Year,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Record {
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year, self.day)
+    }
+}
+
+/// The reason an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong,
+    NotBelow(String),
+    NotAbove(String),
+    Wait(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already rejected."),
+            Refusal::NotBelow(bound) => {
+                write!(
+                    f,
+                    "the answer has to be lower than `{bound}`, which was too high."
+                )
+            }
+            Refusal::NotAbove(bound) => {
+                write!(
+                    f,
+                    "the answer has to be higher than `{bound}`, which was too low."
+                )
+            }
+            Refusal::Wait(duration) => write!(
+                f,
+                "an answer was submitted too recently, wait another {}s.",
+                duration.as_secs()
+            ),
+        }
+    }
+}
+
+/// Represents all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Record>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns no submissions.
+    /// Fails if the file can not be read or parsed, as storing would otherwise overwrite the log.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(SUBMISSIONS_FILE_PATH) {
+            Ok(s) => Submissions::try_from(s)
+                .map_err(|e| format!("could not parse \"{SUBMISSIONS_FILE_PATH}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(format!("could not read \"{SUBMISSIONS_FILE_PATH}\": {e}")),
+        }
+    }
+
+    pub fn push(&mut self, record: Record) {
+        self.data.push(record);
+    }
+
+    /// Check whether an answer should be submitted, given the previous submissions of a puzzle part.
+    pub fn check(&self, puzzle: PuzzleId, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let records = self
+            .data
+            .iter()
+            .filter(|r| r.puzzle() == puzzle && r.part == part);
+
+        let value = answer.parse::<i128>().ok();
+
+        for record in records {
+            match &record.outcome {
+                Outcome::Correct => return Err(Refusal::AlreadySolved(record.answer.clone())),
+                Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+                    if record.answer == answer =>
+                {
+                    return Err(Refusal::KnownWrong)
+                }
+                Outcome::TooHigh if is_bounded(value, &record.answer, |v, bound| v >= bound) => {
+                    return Err(Refusal::NotBelow(record.answer.clone()))
+                }
+                Outcome::TooLow if is_bounded(value, &record.answer, |v, bound| v <= bound) => {
+                    return Err(Refusal::NotAbove(record.answer.clone()))
+                }
+                Outcome::Wait(Some(duration)) => {
+                    let until = record.timestamp + duration.as_secs();
+                    if until > now {
+                        return Err(Refusal::Wait(Duration::from_secs(until - now)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_bounded(value: Option<i128>, bound: &str, f: impl Fn(i128, i128) -> bool) -> bool {
+    match (value, bound.parse::<i128>()) {
+        (Some(value), Ok(bound)) => f(value, bound),
+        _ => false,
+    }
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn outcome_to_json(outcome: &Outcome) -> (JsonValue, JsonValue) {
+    let name = match outcome {
+        Outcome::Correct => "correct",
+        Outcome::TooHigh => "too_high",
+        Outcome::TooLow => "too_low",
+        Outcome::Incorrect => "incorrect",
+        Outcome::Wait(_) => "wait",
+        Outcome::WrongLevel => "wrong_level",
+        Outcome::Unknown => "unknown",
+    };
+
+    let wait = match outcome {
+        #[allow(clippy::cast_precision_loss)]
+        Outcome::Wait(Some(duration)) => JsonValue::Number(duration.as_secs() as f64),
+        _ => JsonValue::Null,
+    };
+
+    (JsonValue::String(name.into()), wait)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn outcome_from_json(name: &str, wait: Option<&JsonValue>) -> Option<Outcome> {
+    Some(match name {
+        "correct" => Outcome::Correct,
+        "too_high" => Outcome::TooHigh,
+        "too_low" => Outcome::TooLow,
+        "incorrect" => Outcome::Incorrect,
+        "wait" => Outcome::Wait(
+            wait.and_then(|v| v.get::<f64>())
+                .map(|secs| Duration::from_secs(*secs as u64)),
+        ),
+        "wrong_level" => Outcome::WrongLevel,
+        "unknown" => Outcome::Unknown,
+        _ => return None,
+    })
+}
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Record::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Record> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let (outcome, wait) = outcome_to_json(&value.outcome);
+
+        map.insert("year".into(), JsonValue::String(value.year.to_string()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert("outcome".into(), outcome);
+        map.insert("wait_secs".into(), wait);
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected submission.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .and_then(|name| outcome_from_json(name, json.get("wait_secs")))
+            .ok_or("Expected submission.outcome to be a known outcome.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|timestamp| *timestamp as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Record {
+            year,
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{Record, Refusal, Submissions};
+    use crate::{
+        day,
+        template::{aoc_client::Outcome, PuzzleId},
+        year,
+    };
+
+    fn record(part: u8, answer: &str, outcome: Outcome) -> Record {
+        Record {
+            year: year!(2024),
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp: 1_000,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                record(1, "11", Outcome::Correct),
+                record(2, "10", Outcome::TooLow),
+                record(2, "50", Outcome::TooHigh),
+                record(2, "abc", Outcome::Incorrect),
+                record(2, "31", Outcome::Wait(Some(Duration::from_secs(60)))),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let submissions = get_mock_submissions();
+        let puzzle = PuzzleId::new(year!(2024), day!(1));
+
+        assert_eq!(
+            submissions.check(puzzle, 1, "12", 2_000),
+            Err(Refusal::AlreadySolved("11".into()))
+        );
+        assert_eq!(
+            submissions.check(puzzle, 2, "abc", 2_000),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(
+            submissions.check(puzzle, 2, "51", 2_000),
+            Err(Refusal::NotBelow("50".into()))
+        );
+        assert_eq!(
+            submissions.check(puzzle, 2, "10", 2_000),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(
+            submissions.check(puzzle, 2, "9", 2_000),
+            Err(Refusal::NotAbove("10".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_while_waiting() {
+        let submissions = get_mock_submissions();
+        let puzzle = PuzzleId::new(year!(2024), day!(1));
+
+        assert_eq!(
+            submissions.check(puzzle, 2, "31", 1_030),
+            Err(Refusal::Wait(Duration::from_secs(30)))
+        );
+        assert_eq!(submissions.check(puzzle, 2, "31", 1_060), Ok(()));
+    }
+
+    #[test]
+    fn allows_other_puzzles() {
+        let submissions = get_mock_submissions();
+        let puzzle = PuzzleId::new(year!(2024), day!(2));
+
+        assert_eq!(submissions.check(puzzle, 1, "11", 1_000), Ok(()));
+        assert_eq!(submissions.check(puzzle, 2, "abc", 1_000), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = get_mock_submissions();
+        let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap().data, submissions.data);
+    }
+}
@@ -1,16 +1,32 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc::AllocStats;
+use crate::template::report::{alloc_from_json, alloc_to_json, stats_from_json, stats_to_json};
+use crate::template::runner::BenchStats;
+use crate::template::{Day, PuzzleId, Year};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Represents the benchmark time of a single part, or of the parse step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// The formatted mean duration, e.g. `1.2ms`.
+    pub duration: String,
+    pub stats: Option<BenchStats>,
+    /// Only present if allocation statistics were enabled, see [`alloc`](crate::template::alloc).
+    pub alloc: Option<AllocStats>,
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
+    pub year: Year,
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Only present for solutions with a shared parse step, see [`solution!`](crate::solution).
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
@@ -46,24 +62,74 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.puzzle() == timing.puzzle()) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(Timing::puzzle);
         Timings { data }
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        // NOTE: folds from `0.0`, as the sum of no floats is `-0.0`.
+        self.data.iter().fold(0.0, |acc, x| acc + x.total_nanos) / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.puzzle() == puzzle && t.part_1.is_some() && t.part_2.is_some())
+    }
+
+    /// Returns the timings of a single year.
+    pub fn for_year(&self, year: Year) -> Self {
+        Timings {
+            data: self
+                .data
+                .iter()
+                .filter(|t| t.year == year)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl PartTiming {
+    pub fn new(duration: impl Into<String>) -> Self {
+        Self {
+            duration: duration.into(),
+            stats: None,
+            alloc: None,
+        }
+    }
+}
+
+impl Timing {
+    /// A timing without any benched parts.
+    pub fn new(puzzle: PuzzleId) -> Self {
+        Self {
+            year: puzzle.year,
+            day: puzzle.day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+        }
+    }
+
+    /// The timings of the parse step and the parts, in this order.
+    pub fn parts(&self) -> [Option<&PartTiming>; 3] {
+        [
+            self.parse.as_ref(),
+            self.part_1.as_ref(),
+            self.part_2.as_ref(),
+        ]
+    }
+
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year, self.day)
     }
 }
 
@@ -87,7 +153,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
 
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
+
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -111,32 +184,76 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("year".into(), JsonValue::String(value.year.to_string()));
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        if value.parse.is_some() {
+            insert_part_timing(&mut map, "parse", value.parse.as_ref());
+        }
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
-
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        insert_part_timing(&mut map, "part_1", value.part_1.as_ref());
+        insert_part_timing(&mut map, "part_2", value.part_2.as_ref());
 
         JsonValue::Object(map)
     }
 }
 
+/// Stores a part timing flat in the timing object, as `<key>`, `<key>_stats` and `<key>_alloc`.
+fn insert_part_timing(
+    map: &mut HashMap<String, JsonValue>,
+    key: &str,
+    timing: Option<&PartTiming>,
+) {
+    map.insert(
+        key.into(),
+        timing.map_or(JsonValue::Null, |t| JsonValue::String(t.duration.clone())),
+    );
+    map.insert(
+        format!("{key}_stats"),
+        stats_to_json(timing.and_then(|t| t.stats)),
+    );
+    map.insert(
+        format!("{key}_alloc"),
+        alloc_to_json(timing.and_then(|t| t.alloc)),
+    );
+}
+
+/// Reads a part timing stored by [`insert_part_timing`]. Only the parse step may be missing.
+fn read_part_timing(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<PartTiming>, String> {
+    let duration = match json.get(key) {
+        // NOTE: the parse step is only present for solutions that share their parsed input.
+        None if key == "parse" => None,
+        Some(v) if v.is_null() => None,
+        v => Some(
+            v.and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or_else(|| format!("Expected timing.{key} to be null or string."))?,
+        ),
+    };
+
+    // NOTE: stats are optional so that timings stored by older versions can still be read.
+    let stats = json
+        .get(&format!("{key}_stats"))
+        .map_or(Ok(None), stats_from_json)
+        .map_err(|_| format!("Expected timing.{key}_stats to be null or a stats object."))?;
+
+    // NOTE: allocation statistics are only present if they were enabled.
+    let alloc = json
+        .get(&format!("{key}_alloc"))
+        .map_or(Ok(None), alloc_from_json)
+        .map_err(|()| format!("Expected timing.{key}_alloc to be null or an alloc object."))?;
+
+    Ok(duration.map(|duration| PartTiming {
+        duration,
+        stats,
+        alloc,
+    }))
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -151,15 +268,12 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        // NOTE: timings stored by older versions do not have a year and belong to the default year.
+        let year = match json.get("year") {
+            Some(v) => v.get::<String>().and_then(|year| Year::from_str(year).ok()),
+            None => Year::from_env(),
+        }
+        .ok_or("Expected timing.year to be a Year struct.")?;
 
         let total_nanos = json
             .get("total_nanos")
@@ -167,9 +281,11 @@ impl TryFrom<&JsonValue> for Timing {
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
         Ok(Timing {
+            year,
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse: read_part_timing(json, "parse")?,
+            part_1: read_part_timing(json, "part_1")?,
+            part_2: read_part_timing(json, "part_2")?,
             total_nanos,
         })
     }
@@ -179,37 +295,37 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::template::{Day, PuzzleId};
+    use crate::{day, year};
+
+    use super::{PartTiming, Timing, Timings};
 
-    use super::{Timing, Timings};
+    fn part(duration: &str) -> Option<PartTiming> {
+        Some(PartTiming::new(duration))
+    }
+
+    fn timing(day: Day, part_1: Option<&str>, part_2: Option<&str>, total_nanos: f64) -> Timing {
+        Timing {
+            part_1: part_1.and_then(part),
+            part_2: part_2.and_then(part),
+            total_nanos,
+            ..Timing::new(PuzzleId::new(year!(2024), day))
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
+                timing(day!(1), Some("10ms"), Some("20ms"), 3e+10),
+                timing(day!(2), Some("30ms"), Some("40ms"), 7e+10),
+                timing(day!(4), Some("40ms"), None, 4e+10),
             ],
         }
     }
 
     mod deserialization {
         use crate::{day, template::timings::Timings};
+        use std::time::Duration;
 
         #[test]
         fn handles_json_timings() {
@@ -218,11 +334,24 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1.as_ref().unwrap().duration, "1ms");
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean_nanos": 1000000, "min_nanos": 900000, "median_nanos": 950000, "p95_nanos": 1200000, "stddev_nanos": 50000, "samples": 100, "outliers": 2 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.as_ref().unwrap().stats.unwrap();
+            assert_eq!(stats.median, Duration::from_nanos(950_000));
+            assert_eq!(stats.p95, Duration::from_nanos(1_200_000));
+            assert_eq!(stats.samples, 100);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(timing.part_2, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -246,10 +375,40 @@ mod tests {
     }
 
     mod serialization {
-        use super::get_mock_timings;
-        use std::collections::HashMap;
+        use super::{get_mock_timings, part};
+        use crate::template::{runner::BenchStats, timings::Timings};
+        use std::{collections::HashMap, time::Duration};
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_stats() {
+            let mut timings = get_mock_timings();
+            let stats = BenchStats {
+                mean: Duration::from_nanos(120),
+                min: Duration::from_nanos(100),
+                median: Duration::from_nanos(110),
+                p95: Duration::from_nanos(150),
+                stddev: Duration::from_nanos(12),
+                samples: 1000,
+                outliers: 7,
+            };
+            timings.data[0].part_1.as_mut().unwrap().stats = Some(stats);
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1.as_ref().unwrap().stats, Some(stats));
+            assert_eq!(parsed.data[0].part_2.as_ref().unwrap().stats, None);
+        }
+
+        #[test]
+        fn roundtrips_parse_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = part("5ms");
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parse, part("5ms"));
+            assert_eq!(parsed.data[1].parse, None);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -269,72 +428,67 @@ mod tests {
     }
 
     mod is_day_complete {
+        use super::timing;
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{timings::Timings, PuzzleId},
+            year,
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
-                }],
+                data: vec![timing(day!(1), Some("1ms"), Some("2ms"), 3_000_000_000_f64)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2024), day!(1))),
+                true
+            );
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
+                data: vec![timing(day!(1), Some("1ms"), None, 1_000_000_000_f64)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2024), day!(1))),
+                false
+            );
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![timing(day!(1), None, None, 0.0)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2024), day!(1))),
+                false
+            );
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                timings::{Timing, Timings},
+                PuzzleId,
+            },
+            year,
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, timing};
 
         #[test]
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![timing(day!(3), None, None, 0_f64)],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +503,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![timing(day!(2), None, None, 0_f64)],
             };
             let merged = timings.merge(&other);
 
@@ -365,6 +514,23 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn handles_timings_of_other_years() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![Timing {
+                    year: year!(2023),
+                    ..timing(day!(2), None, None, 0_f64)
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].puzzle(), PuzzleId::new(year!(2023), day!(2)));
+            assert_eq!(merged.for_year(year!(2024)).data.len(), 3);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,141 @@
+//! Visualizations of simulations, requested with `--visualize <gif|frames|play> [--fps <fps>]`.
+//!
+//! The options are forwarded to the solution binary by `cargo solve`. Solutions read them with the `visualization()`
+//! function generated by [`solution!`](crate::solution) and pass them to a frame
+//! [`Recorder`](crate::bin::util::recorder::Recorder), which stays disabled if there are none:
+//!
+//! ```ignore
+//! let mut recorder = Recorder::new(puzzle(), "part-1", visualization());
+//! ```
+
+use std::{env, str::FromStr, sync::OnceLock, time::Duration};
+
+const DEFAULT_FPS: f64 = 10.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Output {
+    /// An animated GIF, `data/<year>/visualizations/<day>-<name>.gif`.
+    Gif,
+    /// One PNG per frame, `data/<year>/visualizations/<day>-<name>/0001.png`.
+    Frames,
+    /// Replay in the terminal.
+    Play,
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gif" => Ok(Output::Gif),
+            "frames" => Ok(Output::Frames),
+            "play" => Ok(Output::Play),
+            _ => Err(format!(
+                "unknown visualization `{s}`, expected `gif`, `frames` or `play`."
+            )),
+        }
+    }
+}
+
+/// How the frames of a run are visualized, passed to solutions as `--visualize <output> [--fps <fps>]`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Visualization {
+    pub output: Output,
+    pub fps: f64,
+}
+
+impl Visualization {
+    #[must_use]
+    pub fn new(output: Output, fps: Option<f64>) -> Self {
+        Self {
+            output,
+            fps: fps.unwrap_or(DEFAULT_FPS),
+        }
+    }
+
+    /// Reads the visualization from command-line arguments, if any.
+    ///
+    /// # Panics
+    /// If the output or the frame rate are invalid.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let output = value("--visualize")?
+            .parse()
+            .unwrap_or_else(|e| panic!("{e}"));
+        let fps = value("--fps").map(|fps| parse_fps(fps).unwrap_or_else(|e| panic!("{e}")));
+
+        Some(Self::new(output, fps))
+    }
+
+    /// The visualization formatted as command-line arguments, see [`Visualization::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        let output = match self.output {
+            Output::Gif => "gif",
+            Output::Frames => "frames",
+            Output::Play => "play",
+        };
+
+        vec![
+            "--visualize".into(),
+            output.into(),
+            "--fps".into(),
+            self.fps.to_string(),
+        ]
+    }
+
+    pub fn frame_duration(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps)
+    }
+}
+
+/// Parses a positive number of frames per second.
+pub fn parse_fps(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(fps) if fps > 0.0 && fps.is_finite() => Ok(fps),
+        _ => Err(format!(
+            "invalid frame rate `{s}`, expected a positive number."
+        )),
+    }
+}
+
+/// The visualization of the current run. The arguments are only read once.
+/// Timed runs are never visualized, as the parts run repeatedly and would record their frames on every iteration.
+pub fn current() -> Option<Visualization> {
+    static VISUALIZATION: OnceLock<Option<Visualization>> = OnceLock::new();
+
+    *VISUALIZATION.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        if args.iter().any(|arg| arg == "--time") {
+            return None;
+        }
+        Visualization::from_args(&args)
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_fps, Output, Visualization};
+
+    #[test]
+    fn formats_args() {
+        let visualization = Visualization::new(Output::Frames, Some(2.5));
+        assert_eq!(
+            visualization.to_args(),
+            vec!["--visualize", "frames", "--fps", "2.5"]
+        );
+        assert_eq!(
+            Visualization::from_args(&visualization.to_args()),
+            Some(visualization)
+        );
+        assert_eq!("GIF".parse(), Ok(Output::Gif));
+        assert!("mp4".parse::<Output>().is_err());
+        assert!(parse_fps("0").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+/// The first year Advent of Code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of Advent of Code (i.e. 2015 or later).
+///
+/// The default year of the workspace is read from the `AOC_YEAR` environment variable, see [`Year::from_env`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the default year of the workspace, configured with the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Returns the default year of the workspace.
+    ///
+    /// # Panics
+    /// If the `AOC_YEAR` environment variable is not set to a valid year.
+    pub fn default_year() -> Self {
+        Self::from_env()
+            .expect("no default year configured. Set `AOC_YEAR` in `.cargo/config.toml`.")
+    }
+
+    /// Returns whether this is the default year of the workspace.
+    pub fn is_default(self) -> bool {
+        Self::from_env() == Some(self)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle by its year and day.
+///
+/// # Layout
+/// Data files are namespaced by year, e.g. `data/2024/inputs/01.txt`.
+/// Solutions of the default year live in `src/bin/01.rs`, solutions of other years in `src/bin/2023-01.rs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The name of the solution binary of this puzzle.
+    pub fn bin_name(self) -> String {
+        if self.year.is_default() {
+            self.day.to_string()
+        } else {
+            format!("{}-{}", self.year, self.day)
+        }
+    }
+
+    /// The path of the solution module of this puzzle.
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// The path of a data file of this puzzle, e.g. `data/2024/inputs/01.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> PathBuf {
+        self.data_path_with_suffix(folder, "", extension)
+    }
+
+    /// The path of a data file of this puzzle with a suffix appended to the day, e.g. `data/2024/examples/01-2.txt`.
+    pub fn data_path_with_suffix(self, folder: &str, suffix: &str, extension: &str) -> PathBuf {
+        self.data_dir(folder)
+            .join(format!("{}{suffix}.{extension}", self.day))
+    }
+
+    /// The directory of the data files of this puzzle's year, e.g. `data/2024/examples`.
+    pub fn data_dir(self, folder: &str) -> PathBuf {
+        PathBuf::from("data")
+            .join(self.year.to_string())
+            .join(folder)
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the current puzzle if it's between the 1st and the 25th of december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let day = Day::today()?;
+        let offset = FixedOffset::east_opt(super::day::SERVER_UTC_OFFSET * 3600)?;
+        let year = Utc::now().with_timezone(&offset).year();
+        Some(Self::new(Year::new(u16::try_from(year).ok()?)?, day))
+    }
+}
+
+/// Puzzles referenced by day only belong to the default year of the workspace.
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self::new(Year::default_year(), day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, Year};
+    use crate::day;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("twenty".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn namespaces_data_paths_by_year() {
+        let id = PuzzleId::new(Year(2023), day!(5));
+        assert_eq!(
+            id.data_path("inputs", "txt"),
+            PathBuf::from("data/2023/inputs/05.txt")
+        );
+        assert_eq!(
+            id.data_path_with_suffix("examples", "-2", "txt"),
+            PathBuf::from("data/2023/examples/05-2.txt")
+        );
+    }
+
+    #[test]
+    fn names_bins_by_year() {
+        // NOTE: `AOC_YEAR` is configured in `.cargo/config.toml`.
+        let default_year = Year::default_year();
+        assert_eq!(PuzzleId::new(default_year, day!(5)).bin_name(), "05");
+        assert_eq!(PuzzleId::new(Year(2015), day!(5)).bin_name(), "2015-05");
+    }
+}